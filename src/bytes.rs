@@ -0,0 +1,89 @@
+use super::LimitedVec;
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Write;
+
+/// A byte buffer which serializes as a hex string for human readable formats and as raw bytes
+/// otherwise
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct LimitedBytes<const N: usize>(pub LimitedVec<u8, N>);
+
+impl<const N: usize> From<LimitedVec<u8, N>> for LimitedBytes<N> {
+    #[inline]
+    fn from(bytes: LimitedVec<u8, N>) -> Self {
+        LimitedBytes(bytes)
+    }
+}
+
+impl<const N: usize> Serialize for LimitedBytes<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let mut hex = String::with_capacity(self.0.len() * 2);
+            for b in self.0.iter() {
+                write!(hex, "{b:02x}").unwrap();
+            }
+            return serializer.serialize_str(&hex);
+        }
+
+        let mut buf = [0u8; N];
+        for (dst, src) in buf.iter_mut().zip(self.0.iter()) {
+            *dst = *src;
+        }
+        serializer.serialize_bytes(&buf[..self.0.len()])
+    }
+}
+
+struct LimitedBytesVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for LimitedBytesVisitor<N> {
+    type Value = LimitedBytes<N>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a hex string or bytes with at most {N} bytes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if !v.len().is_multiple_of(2) || v.len() / 2 > N {
+            return Err(E::invalid_length(v.len() / 2, &self));
+        }
+        let mut bytes = LimitedVec::default();
+        for pair in v.as_bytes().chunks(2) {
+            // from_str_radix would also accept a sign like "+a"
+            let byte = Some(pair)
+                .filter(|pair| pair.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|pair| std::str::from_utf8(pair).ok())
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))?;
+            bytes.push(byte);
+        }
+        Ok(LimitedBytes(bytes))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if v.len() > N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        Ok(LimitedBytes(v.iter().copied().collect()))
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for LimitedBytes<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(LimitedBytesVisitor)
+        } else {
+            deserializer.deserialize_bytes(LimitedBytesVisitor)
+        }
+    }
+}
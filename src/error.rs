@@ -0,0 +1,66 @@
+use std::fmt::{Display, Formatter};
+
+/// Error returned when an iterator doesn't yield the required amount of items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromIterError {
+    /// The iterator yielded only the given amount of items
+    NotEnough(usize),
+    /// The iterator yielded more items than the LimitedVec can hold
+    TooMany,
+}
+
+impl Display for FromIterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FromIterError::NotEnough(count) => write!(f, "Iterator yielded only {count} items"),
+            FromIterError::TooMany => write!(f, "Iterator yielded more items than capacity"),
+        }
+    }
+}
+
+impl std::error::Error for FromIterError {}
+
+/// Error returned when an item doesn't fit into a LimitedVec. Holds the rejected item.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    item: T,
+}
+
+impl<T> CapacityError<T> {
+    #[inline]
+    pub fn new(item: T) -> Self {
+        Self { item }
+    }
+
+    /// Returns the rejected item
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl<T> std::fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CapacityError: {self}")
+    }
+}
+
+impl<T> Display for CapacityError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LimitedVec is full")
+    }
+}
+
+impl<T> std::error::Error for CapacityError<T> {}
+
+/// Error returned when accessing an item of an empty LimitedVec
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyError;
+
+impl Display for EmptyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LimitedVec is empty")
+    }
+}
+
+impl std::error::Error for EmptyError {}
@@ -1,14 +1,36 @@
+use super::view::SubView;
 use super::LimitedVec;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::Range;
 
 pub struct Iter<'a, T, const N: usize> {
     lvec: &'a LimitedVec<T, N>,
     pos: usize,
+    end: usize,
 }
 
 impl<'a, T, const N: usize> Iter<'a, T, N> {
     #[inline]
     pub fn new(lvec: &'a LimitedVec<T, N>) -> Self {
-        Self { lvec, pos: 0 }
+        let end = lvec.len();
+        Self { lvec, pos: 0, end }
+    }
+
+    /// Creates an iterator over the items within `range`, which must not exceed the length
+    #[inline]
+    pub(crate) fn with_range(lvec: &'a LimitedVec<T, N>, range: Range<usize>) -> Self {
+        Self {
+            lvec,
+            pos: range.start,
+            end: range.end,
+        }
+    }
+
+    /// Returns the amount of items which haven't been iterated yet
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.end - self.pos
     }
 }
 
@@ -17,8 +39,354 @@ impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let item = self.lvec.get(self.pos)?;
+        if self.pos >= self.end {
+            return None;
+        }
+        // `pos..end` lies within the items, so the slot is read directly instead of rechecking
+        // the length via `get`
+        let item = self.lvec.0[self.pos].as_ref();
+        self.pos += 1;
+        item
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.pos = self.pos.saturating_add(n).min(self.end);
+        self.next()
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Iter<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        self.lvec.0[self.end].as_ref()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.end = self.end.saturating_sub(n).max(self.pos);
+        self.next_back()
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for Iter<'a, T, N> {}
+
+impl<'a, T, const N: usize> FusedIterator for Iter<'a, T, N> {}
+
+pub struct IterMut<'a, T, const N: usize> {
+    slots: std::slice::IterMut<'a, Option<T>>,
+}
+
+impl<'a, T, const N: usize> IterMut<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(slots: &'a mut [Option<T>]) -> Self {
+        Self {
+            slots: slots.iter_mut(),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for IterMut<'a, T, N> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(Option::as_mut)
+    }
+}
+
+pub struct ChunksMut<'a, T, const N: usize> {
+    chunks: std::slice::ChunksMut<'a, Option<T>>,
+}
+
+impl<'a, T, const N: usize> ChunksMut<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(slots: &'a mut [Option<T>], size: usize) -> Self {
+        Self {
+            chunks: slots.chunks_mut(size),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for ChunksMut<'a, T, N> {
+    type Item = IterMut<'a, T, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(IterMut::new)
+    }
+}
+
+pub struct Drain<'a, T, const N: usize> {
+    items: IntoIter<T, N>,
+    lvec: PhantomData<&'a mut LimitedVec<T, N>>,
+}
+
+impl<'a, T, const N: usize> Drain<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(lvec: &'a mut LimitedVec<T, N>, range: Range<usize>) -> Self {
+        let len = lvec.len();
+        // Move the drained items out and the tail into place right away, so the LimitedVec stays
+        // packed even if the Drain gets leaked or dropping an item panics
+        let mut drained = LimitedVec::default();
+        drained.0[..range.len()].swap_with_slice(&mut lvec.0[range.clone()]);
+        lvec.0[range.start..len].rotate_left(range.len());
+        Self {
+            items: IntoIter::new(drained),
+            lvec: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Drain<'a, T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Drain<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back()
+    }
+}
+
+pub struct IntoIter<T, const N: usize> {
+    slots: [Option<T>; N],
+    pos: usize,
+    end: usize,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    #[inline]
+    pub(crate) fn new(lvec: LimitedVec<T, N>) -> Self {
+        let end = lvec.len();
+        Self {
+            slots: lvec.0,
+            pos: 0,
+            end,
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
+        }
+        let item = self.slots[self.pos].take()?;
         self.pos += 1;
         Some(item)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        self.slots[self.end].take()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+pub struct IndexIter<'a, T, const N: usize> {
+    lvec: &'a LimitedVec<T, N>,
+    indices: Range<usize>,
+}
+
+impl<'a, T, const N: usize> IndexIter<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(lvec: &'a LimitedVec<T, N>) -> Self {
+        Self {
+            lvec,
+            indices: 0..N,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for IndexIter<'a, T, N> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.find(|&i| self.lvec.0[i].is_some())
+    }
+}
+
+pub struct RChunksExact<'a, T, const N: usize> {
+    lvec: &'a LimitedVec<T, N>,
+    size: usize,
+    remainder: usize,
+    end: usize,
+}
+
+impl<'a, T, const N: usize> RChunksExact<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(lvec: &'a LimitedVec<T, N>, size: usize) -> Self {
+        let end = lvec.len();
+        Self {
+            lvec,
+            size,
+            remainder: end % size,
+            end,
+        }
+    }
+
+    /// Returns the leading items which don't make up a complete chunk
+    #[inline]
+    pub fn remainder(&self) -> Iter<'a, T, N> {
+        Iter::with_range(self.lvec, 0..self.remainder)
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for RChunksExact<'a, T, N> {
+    type Item = Iter<'a, T, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end < self.remainder + self.size {
+            return None;
+        }
+        self.end -= self.size;
+        Some(Iter::with_range(self.lvec, self.end..self.end + self.size))
+    }
+}
+
+pub struct Windows<'a, T, const N: usize> {
+    lvec: &'a LimitedVec<T, N>,
+    size: usize,
+    pos: usize,
+    len: usize,
+}
+
+impl<'a, T, const N: usize> Windows<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(lvec: &'a LimitedVec<T, N>, size: usize) -> Self {
+        let len = lvec.len();
+        Self {
+            lvec,
+            size,
+            pos: 0,
+            len,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Windows<'a, T, N> {
+    type Item = SubView<'a, T, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.pos.checked_add(self.size)?;
+        if end > self.len {
+            return None;
+        }
+        let window = SubView::new(self.lvec, self.pos..end);
+        self.pos += 1;
+        Some(window)
+    }
+}
+
+pub struct Chunks<'a, T, const N: usize> {
+    lvec: &'a LimitedVec<T, N>,
+    size: usize,
+    pos: usize,
+    len: usize,
+}
+
+impl<'a, T, const N: usize> Chunks<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(lvec: &'a LimitedVec<T, N>, size: usize) -> Self {
+        let len = lvec.len();
+        Self {
+            lvec,
+            size,
+            pos: 0,
+            len,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Chunks<'a, T, N> {
+    type Item = SubView<'a, T, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.len {
+            return None;
+        }
+        let end = self.pos.saturating_add(self.size).min(self.len);
+        let chunk = SubView::new(self.lvec, self.pos..end);
+        self.pos = end;
+        Some(chunk)
+    }
+}
+
+pub struct PeekBuffer<'a, T, const N: usize> {
+    iter: Iter<'a, T, N>,
+}
+
+impl<'a, T, const N: usize> PeekBuffer<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(iter: Iter<'a, T, N>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the item `n` positions ahead without consuming anything, so `peek_ahead(0)` is the
+    /// item `next()` would return
+    #[inline]
+    pub fn peek_ahead(&self, n: usize) -> Option<&'a T> {
+        let pos = self.iter.pos.checked_add(n)?;
+        if pos >= self.iter.end {
+            return None;
+        }
+        self.iter.lvec.0[pos].as_ref()
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for PeekBuffer<'a, T, N> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
@@ -1,12 +1,101 @@
+#[cfg(feature = "with_serde")]
+pub mod bytes;
+pub mod error;
 pub mod iter;
+pub mod view;
 
-use core::ops::Index;
-use iter::Iter;
+use core::hash::{Hash, Hasher};
+use core::ops::{Bound, Index, Range, RangeBounds};
+use error::{CapacityError, EmptyError, FromIterError};
+use iter::{
+    Chunks, ChunksMut, Drain, IndexIter, IntoIter, Iter, IterMut, PeekBuffer, RChunksExact, Windows,
+};
+use std::borrow::Cow;
 
 #[cfg(feature = "with_serde")]
 use serde::{de::Visitor, ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Clone, PartialEq, Eq)]
+/// Creates a LimitedVec with capacity `cap` holding the listed items, similar to `vec![]`, eg.
+/// `limvec![cap = 8; 1, 2, 3]`. Fails to compile if there are more items than `cap`.
+#[macro_export]
+macro_rules! limvec {
+    (@unit $item:expr) => {
+        ()
+    };
+    (cap = $cap:expr;) => {
+        $crate::LimitedVec::<_, { $cap }>::default()
+    };
+    (cap = $cap:expr; $($item:expr),+ $(,)?) => {{
+        const _: () = assert!(
+            <[()]>::len(&[$($crate::limvec!(@unit $item)),+]) <= $cap,
+            "Can't create a LimitedVec with more items than its capacity"
+        );
+        let mut lvec = $crate::LimitedVec::<_, { $cap }>::default();
+        $(lvec.push($item);)+
+        lvec
+    }};
+}
+
+/// Where to insert an item whose key equals the key of already present items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DupPolicy {
+    /// Insert the item before all items with an equal key
+    AllowBefore,
+    /// Insert the item after all items with an equal key
+    AllowAfter,
+    /// Don't insert the item if there is an item with an equal key
+    Reject,
+}
+
+/// What `retain_controlled` does with an item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetainAction {
+    /// Keep the item
+    Keep,
+    /// Remove the item
+    Remove,
+    /// Keep the item and all following items without checking them
+    StopKeepingRest,
+}
+
+/// How many items `retain_stats` kept and removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetainStats {
+    /// The amount of kept items
+    pub kept: usize,
+    /// The amount of removed items
+    pub removed: usize,
+}
+
+/// A clone operation which can fail, eg. for resource handles
+pub trait TryClone: Sized {
+    type Error;
+
+    /// Tries to create a copy of the value
+    fn try_clone(&self) -> Result<Self, Self::Error>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A container with a fixed capacity, for generic code over LimitedVecs of any capacity. This
+/// trait is sealed and can't be implemented outside of this crate.
+pub trait BoundedCapacity: sealed::Sealed {
+    /// The amount of items the container can hold
+    const CAPACITY: usize;
+
+    /// Returns the amount of items in the container
+    fn current_len(&self) -> usize;
+}
+
+/// A list holding up to `N` items without allocating.
+///
+/// The items are always packed at the front: the first `len()` slots are occupied and all
+/// following slots are free. Every method relies on this and keeps it intact. Only raw slot
+/// access via `as_mut_array` can break it, in which case `compact()` has to be called before
+/// using the LimitedVec again. Debug builds assert the invariant when computing the length.
+#[derive(Clone)]
 pub struct LimitedVec<T, const N: usize>([Option<T>; N]);
 
 impl<T, const N: usize> LimitedVec<T, N>
@@ -18,304 +107,3359 @@ where
     pub fn new() -> Self {
         LimitedVec([None; N])
     }
+
+    /// Creates a LimitedVec holding copies of the items in `src`. Panics if `src` has more than
+    /// `N` items.
+    pub fn from_slice(src: &[T]) -> Self {
+        let len = src.len();
+        assert!(
+            len <= N,
+            "Can't create a LimitedVec with capacity of {N} from {len} items"
+        );
+        let mut lvec = Self::new();
+        for (slot, item) in lvec.0.iter_mut().zip(src) {
+            *slot = Some(*item);
+        }
+        lvec
+    }
+
+    /// Copies the items into arrays of `M` items and a remainder of less than `M` items.
+    /// The items are copied since the slots of a LimitedVec are not a contiguous `[T]`.
+    /// Fails to compile if `M` is 0.
+    pub fn to_chunks<const M: usize>(&self) -> (Vec<[T; M]>, Vec<T>) {
+        const { assert!(M != 0, "Chunk size must be non-zero") };
+        let len = self.len();
+        let split = len - len % M;
+        let chunks = (0..split)
+            .step_by(M)
+            .map(|start| std::array::from_fn(|i| self[start + i]))
+            .collect();
+        let remainder = self.iter().skip(split).copied().collect();
+        (chunks, remainder)
+    }
+}
+
+impl<A, B, const N: usize> LimitedVec<(A, B), N> {
+    /// Splits the pairs into two LimitedVecs holding the first and second items
+    pub fn unzip(self) -> (LimitedVec<A, N>, LimitedVec<B, N>) {
+        let mut left = LimitedVec::default();
+        let mut right = LimitedVec::default();
+        for (pos, (a, b)) in self.into_iter().enumerate() {
+            left.0[pos] = Some(a);
+            right.0[pos] = Some(b);
+        }
+        (left, right)
+    }
+}
+
+impl<T, const N: usize> Default for LimitedVec<T, N> {
+    /// Creates a new empty limited vector
+    #[inline]
+    fn default() -> Self {
+        LimitedVec([(); N].map(|_| None))
+    }
 }
 
 impl<T, const N: usize> LimitedVec<T, N> {
-    /// Pushes a new value onto the LimitedVec
+    /// Creates a full LimitedVec with all items set to `T::default()`
+    #[inline]
+    pub fn filled_default() -> Self
+    where
+        T: Default,
+    {
+        LimitedVec([(); N].map(|_| Some(T::default())))
+    }
+
+    /// Creates a full LimitedVec from an iterator which has to yield exactly `N` items
+    pub fn from_iter_exact<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, FromIterError> {
+        let mut lvec = Self::default();
+        for (pos, i) in iter.into_iter().enumerate() {
+            if pos >= N {
+                return Err(FromIterError::TooMany);
+            }
+            lvec.0[pos] = Some(i);
+        }
+        let len = lvec.len();
+        if len < N {
+            return Err(FromIterError::NotEnough(len));
+        }
+        Ok(lvec)
+    }
+
+    /// Creates a LimitedVec from an iterator, storing the items in reverse order so the last
+    /// yielded item ends up at the front. Panics if there are more than `N` items.
+    pub fn from_iter_rev<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut lvec: Self = iter.into_iter().collect();
+        let len = lvec.len();
+        lvec.0[..len].reverse();
+        lvec
+    }
+
+    /// Creates a LimitedVec from its slots. Panics if there is an occupied slot after a free one.
+    #[inline]
+    pub fn from_option_array(slots: [Option<T>; N]) -> Self {
+        match Self::try_from_option_array(slots) {
+            Ok(lvec) => lvec,
+            Err(_) => panic!("Occupied slots must not follow a free slot"),
+        }
+    }
+
+    /// Creates a LimitedVec from its slots, returning them back if there is an occupied slot after
+    /// a free one
+    pub fn try_from_option_array(slots: [Option<T>; N]) -> Result<Self, [Option<T>; N]> {
+        let lvec = LimitedVec(slots);
+        if !lvec.is_packed() {
+            return Err(lvec.0);
+        }
+        Ok(lvec)
+    }
+
+    /// Pushes a new value onto the LimitedVec. Panics if it's full. See [`try_push`] to get the
+    /// item back and [`saturating_push`] to drop it instead.
+    ///
+    /// [`try_push`]: LimitedVec::try_push
+    /// [`saturating_push`]: LimitedVec::saturating_push
     #[inline]
     pub fn push(&mut self, item: T) {
-        match self.next_mut() {
-            Some(m) => *m = Some(item),
-            None => panic!("Trying to push more elements than SmallVec can hold"),
+        if self.try_push(item).is_err() {
+            panic!("Trying to push more elements than SmallVec can hold");
         }
     }
 
     /// Pops the last element and return
     #[inline]
+    #[must_use]
     pub fn pop(&mut self) -> Option<T> {
         let last_idx = self.last_idx()?;
-        std::mem::replace(&mut self.0[last_idx], None)
+        self.0[last_idx].take()
     }
 
-    /// Returns the count of items the vector is holding
-    pub fn len(&self) -> usize {
-        self.0.iter().take_while(|i| i.is_some()).count()
+    /// Pushes a new value onto the LimitedVec or returns it back in an error if it's full. See
+    /// also [`push`] and [`saturating_push`].
+    ///
+    /// [`push`]: LimitedVec::push
+    /// [`saturating_push`]: LimitedVec::saturating_push
+    #[inline]
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        match self.next_mut() {
+            Some(m) => {
+                *m = Some(item);
+                Ok(())
+            }
+            None => Err(CapacityError::new(item)),
+        }
     }
 
-    /// Returns `true` if there is no item pushed onto the LimitedVec
+    /// Pushes a new value onto the LimitedVec and returns `true`, or drops it and returns `false`
+    /// if it's full. See also [`push`] and [`try_push`].
+    ///
+    /// [`push`]: LimitedVec::push
+    /// [`try_push`]: LimitedVec::try_push
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    pub fn saturating_push(&mut self, item: T) -> bool {
+        self.try_push(item).is_ok()
     }
 
-    /// Returns the amount of items the LimitedVec can hold
+    /// Inserts an item at `index` and shifts all following items to the right.
+    /// Panics if `index > len` or the LimitedVec is full. See [`try_insert`] and
+    /// [`saturating_insert`] for the non-panicking variants.
+    ///
+    /// [`try_insert`]: LimitedVec::try_insert
+    /// [`saturating_insert`]: LimitedVec::saturating_insert
     #[inline]
-    pub fn capacity(&self) -> usize {
-        N
+    pub fn insert(&mut self, index: usize, item: T) {
+        if self.try_insert(index, item).is_err() {
+            panic!("Trying to insert more elements than LimitedVec can hold");
+        }
     }
 
-    /// Returns the amount of free slots which can be used to push more items
-    pub fn free(&self) -> usize {
-        self.0.iter().rev().take_while(|i| i.is_none()).count()
+    /// Inserts an item at `index` and shifts all following items to the right. Returns the item
+    /// back in an error if the LimitedVec is full. Panics if `index > len`. See also [`insert`] and
+    /// [`saturating_insert`].
+    ///
+    /// [`insert`]: LimitedVec::insert
+    /// [`saturating_insert`]: LimitedVec::saturating_insert
+    pub fn try_insert(&mut self, index: usize, item: T) -> Result<(), CapacityError<T>> {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "Index {index} out of bounds with length of {len}"
+        );
+        if len == N {
+            return Err(CapacityError::new(item));
+        }
+        self.0[len] = Some(item);
+        self.0[index..=len].rotate_right(1);
+        Ok(())
     }
 
-    /// Returns `true` if there is no free slot left
+    /// Inserts an item at `index` and returns `true`, or drops it and returns `false` if the
+    /// LimitedVec is full. Panics if `index > len`. See also [`insert`] and [`try_insert`].
+    ///
+    /// [`insert`]: LimitedVec::insert
+    /// [`try_insert`]: LimitedVec::try_insert
     #[inline]
-    pub fn is_full(&self) -> bool {
-        self.free() == 0
+    pub fn saturating_insert(&mut self, index: usize, item: T) -> bool {
+        self.try_insert(index, item).is_ok()
     }
 
-    /// Gets an item by its index
-    #[inline]
-    pub fn get(&self, pos: usize) -> Option<&T> {
-        if pos >= self.len() {
+    /// Inserts an item into the items sorted by `key` and returns its index. `dup` decides where
+    /// the item goes if there are items with an equal key. Returns the item back if it got
+    /// rejected. Panics if the LimitedVec is full.
+    pub fn insert_sorted_by_key<K: Ord, F: FnMut(&T) -> K>(
+        &mut self,
+        item: T,
+        mut key: F,
+        dup: DupPolicy,
+    ) -> Result<usize, T> {
+        let item_key = key(&item);
+        let index = match dup {
+            DupPolicy::AllowBefore => self.partition_point(|i| key(i) < item_key),
+            DupPolicy::AllowAfter => self.partition_point(|i| key(i) <= item_key),
+            DupPolicy::Reject => {
+                let index = self.partition_point(|i| key(i) < item_key);
+                if self.get(index).is_some_and(|i| key(i) == item_key) {
+                    return Err(item);
+                }
+                index
+            }
+        };
+        self.insert(index, item);
+        Ok(index)
+    }
+
+    /// Replaces the item at `index` with `value` and returns the old item. Panics if `index` is
+    /// out of bounds.
+    #[must_use]
+    pub fn replace_at(&mut self, index: usize, value: T) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "Index {index} out of bounds with length of {len}"
+        );
+        self.0[index].replace(value).unwrap()
+    }
+
+    /// Removes and returns the first item if the LimitedVec is full, eg. to make room for a push
+    /// in a sliding window. Returns None otherwise.
+    pub fn pop_front_if_full(&mut self) -> Option<T> {
+        if self.is_empty() || !self.is_full() {
             return None;
         }
-        Some(&self[pos])
+        Some(self.remove(0))
     }
 
-    /// Returns the last item of the LimitedVec or None if its empty.
-    pub fn last_mut(&mut self) -> Option<&mut T> {
-        let last_pos = self.last_idx()?;
-        self.0[last_pos].as_mut()
+    /// Removes the item at `index` and shifts all following items to the left.
+    /// Panics if `index` is out of bounds. Use `let _ = lvec.remove(index);` to discard the item.
+    #[must_use]
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "Index {index} out of bounds with length of {len}"
+        );
+        let item = self.0[index].take();
+        self.0[index..len].rotate_left(1);
+        item.unwrap()
     }
 
-    /// Returns the last item of the LimitedVec or None if its empty.
-    pub fn last(&self) -> Option<&T> {
-        let last_pos = self.last_idx()?;
-        self.0[last_pos].as_ref()
+    /// Removes the item at `index` and replaces it with the last item. This doesn't preserve the
+    /// order but is O(1). Panics if `index` is out of bounds. Use `let _ = lvec.swap_remove(index);`
+    /// to discard the item.
+    #[must_use]
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "Index {index} out of bounds with length of {len}"
+        );
+        // Swapping is a no-op when removing the last item, which is taken directly
+        self.0.swap(index, len - 1);
+        let item = self.0[len - 1].take();
+        debug_assert!(self.is_packed());
+        item.unwrap()
     }
 
-    /// Returns the index of the last item with a value or None if the LimitedVec is empty.
-    #[inline]
-    pub fn last_idx(&self) -> Option<usize> {
-        self.len().checked_sub(1)
+    /// Moves the item at `index` to the front, shifting the items before it one to the right.
+    /// Panics if `index` is out of bounds.
+    pub fn rotate_to_front(&mut self, index: usize) {
+        let len = self.len();
+        assert!(
+            index < len,
+            "Index {index} out of bounds with length of {len}"
+        );
+        self.0[..=index].rotate_right(1);
     }
 
-    #[inline]
-    pub fn iter(&self) -> Iter<'_, T, N> {
-        Iter::new(self)
+    /// Removes the items at all `indices` like `swap_remove` and returns them ordered from the
+    /// highest index to the lowest one. Panics if an index is out of bounds or listed twice.
+    pub fn swap_remove_many(&mut self, indices: &[usize]) -> LimitedVec<T, N> {
+        let mut sorted = indices.to_vec();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        let len = self.len();
+        if let Some(&highest) = sorted.first() {
+            assert!(
+                highest < len,
+                "Index {highest} out of bounds with length of {len}"
+            );
+        }
+        assert!(
+            sorted.windows(2).all(|w| w[0] != w[1]),
+            "Indices must be unique"
+        );
+        // Removing the highest index first only moves items which aren't removed afterwards
+        let mut removed = Self::default();
+        for index in sorted {
+            removed.push(self.swap_remove(index));
+        }
+        removed
     }
 
-    /// Returns the next empty allocated item
-    #[inline]
-    fn next_mut(&mut self) -> Option<&mut Option<T>> {
-        self.0.iter_mut().find(|i| i.is_none())
+    /// Splits off all items from `at` into a new LimitedVec. Panics if `at > len`.
+    #[must_use]
+    pub fn split_off(&mut self, at: usize) -> LimitedVec<T, N> {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "Split index {at} out of bounds with length of {len}"
+        );
+        let mut other = Self::default();
+        for (pos, slot) in self.0[at..len].iter_mut().enumerate() {
+            other.0[pos] = slot.take();
+        }
+        other
     }
-}
 
-impl<T, const N: usize> Index<usize> for LimitedVec<T, N> {
-    type Output = T;
+    /// Returns a reference to the slots backing the LimitedVec
+    #[inline]
+    pub fn as_array(&self) -> &[Option<T>; N] {
+        &self.0
+    }
 
+    /// Returns a mutable reference to the slots backing the LimitedVec.
+    ///
+    /// All occupied slots have to come before the free ones. If the slots get mutated in a way
+    /// which leaves free slots in between, call `compact()` before using the LimitedVec again.
     #[inline]
-    fn index(&self, index: usize) -> &Self::Output {
-        match &self.0[index] {
-            Some(e) => e,
-            None => {
-                let cap = self.capacity();
-                panic!("Index {index} out of bounds with capacity of {cap}",)
+    pub fn as_mut_array(&mut self) -> &mut [Option<T>; N] {
+        &mut self.0
+    }
+
+    /// Moves all occupied slots in front of the free ones, keeping the order of the items
+    pub fn compact(&mut self) {
+        let mut kept = 0;
+        for pos in 0..N {
+            if self.0[pos].is_some() {
+                self.0.swap(kept, pos);
+                kept += 1;
             }
         }
     }
-}
 
-impl<T, const N: usize> From<Vec<T>> for LimitedVec<T, N> {
-    fn from(values: Vec<T>) -> Self {
-        if values.len() > N {
-            panic!("Vec is larger than LimitedVec's capacity");
-        }
-        let free = N - values.len();
-        let free_iter = (0..free).map(|_| None);
-        let d: [Option<T>; N] = values
-            .into_iter()
-            .map(|i| Some(i))
-            .chain(free_iter)
-            .collect::<Vec<_>>()
-            .try_into()
-            .ok()
-            .unwrap();
-        LimitedVec(d)
+    /// Returns the slots backing the LimitedVec
+    #[inline]
+    pub fn into_option_array(self) -> [Option<T>; N] {
+        self.0
     }
-}
 
-impl<T, const N: usize> FromIterator<T> for LimitedVec<T, N> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut buf = Vec::with_capacity(N);
-        for i in iter.into_iter() {
-            buf.push(Some(i));
-            if buf.len() > N {
-                panic!("Can't collect more elements into LimitedVec than capacity (N)");
-            }
-        }
-        if buf.len() < N {
-            let free_iter = (0..(N - buf.len())).map(|_| None);
-            buf.extend(free_iter);
-        }
-        LimitedVec(buf.try_into().ok().unwrap())
+    /// Splits the items into `K` parts of nearly equal length, where the leading parts get one
+    /// item more if the length isn't divisible by `K`. Panics if a part doesn't fit into the
+    /// capacity `P`. Fails to compile if `K` is 0.
+    pub fn split_into<const K: usize, const P: usize>(self) -> [LimitedVec<T, P>; K] {
+        const { assert!(K != 0, "Can't split LimitedVec into 0 parts") };
+        let len = self.len();
+        let (base, extra) = (len / K, len % K);
+        let largest = base + usize::from(extra > 0);
+        assert!(
+            largest <= P,
+            "Can't split {len} items into {K} parts with capacity of {P}"
+        );
+        let mut items = self.into_iter();
+        std::array::from_fn(|part| {
+            let size = base + usize::from(part < extra);
+            items.by_ref().take(size).collect()
+        })
     }
-}
 
-impl<T, const N: usize> Extend<T> for LimitedVec<T, N> {
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        let start_idx = self.last_idx().map(|i| i + 1).unwrap_or(0);
-        for (pos, i) in iter.into_iter().enumerate() {
-            let dst_idx = start_idx + pos;
-            if dst_idx >= N {
-                panic!("Can't push more elements than LimitedVec capacity!");
-            }
-            self.0[dst_idx] = Some(i);
+    /// Moves all items into a LimitedVec with a capacity of `M`, returning the LimitedVec back if
+    /// its items don't fit
+    pub fn try_into_capacity<const M: usize>(self) -> Result<LimitedVec<T, M>, Self> {
+        if self.len() > M {
+            return Err(self);
         }
+        Ok(self.into_iter().collect())
     }
-}
 
-impl<T: std::fmt::Debug, const N: usize> std::fmt::Debug for LimitedVec<T, N> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
+    /// Clones all items into a Vec
+    #[inline]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
     }
-}
 
-#[cfg(feature = "with_serde")]
-impl<T, const N: usize> Serialize for LimitedVec<T, N>
-where
-    T: Serialize,
-{
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    /// Concatenates all string items into one String
+    pub fn concat(&self) -> String
     where
-        S: Serializer,
+        T: AsRef<str>,
     {
-        //serializer.serialize_i32(*self)
-        let mut list = serializer.serialize_seq(Some(self.len()))?;
-        for i in self.iter() {
-            list.serialize_element(i)?;
-        }
-        list.end()
+        self.iter().map(AsRef::as_ref).collect()
     }
-}
 
-#[cfg(feature = "with_serde")]
-pub struct LimitedVecVisitor<T, const N: usize> {
-    pd: std::marker::PhantomData<T>,
-}
+    /// Returns the items as a slice. The items are stored as `Option<T>` slots, so this currently
+    /// always clones them into an owned Vec and allocates; use `iter()` to avoid the copy.
+    #[inline]
+    pub fn as_slice_cow(&self) -> Cow<'_, [T]>
+    where
+        T: Clone,
+    {
+        Cow::Owned(self.to_vec())
+    }
 
-#[cfg(feature = "with_serde")]
-impl<'de, T, const N: usize> Visitor<'de> for LimitedVecVisitor<T, N>
-where
-    T: Deserialize<'de>,
-{
-    type Value = LimitedVec<T, N>;
+    /// Moves all items into a Vec
+    #[inline]
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        self.into_iter().collect()
+    }
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("Error deserializing")
+    /// Drops the fixed capacity and returns only the occupied items. Same as `into_vec`.
+    #[inline]
+    #[must_use]
+    pub fn trim(self) -> Vec<T> {
+        self.into_vec()
     }
 
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    /// Removes all items after the first `len` items
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+        // Detach the removed items before dropping them all at once, so the LimitedVec is
+        // consistent even if dropping an item panics and no item gets dropped twice
+        let mut removed = Self::default();
+        removed.0[..old_len - len].swap_with_slice(&mut self.0[len..old_len]);
+        drop(removed);
+    }
+
+    /// Removes all items before the last `len` items and shifts the remaining items to the front
+    pub fn truncate_front(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+        self.0[..old_len].rotate_left(old_len - len);
+        self.truncate(len);
+    }
+
+    /// Resizes the LimitedVec to `new_len` items by either truncating it or appending
+    /// `T::default()` values. Panics if `new_len` exceeds the capacity.
+    pub fn resize_default(&mut self, new_len: usize)
     where
-        A: serde::de::SeqAccess<'de>,
+        T: Default,
     {
-        let mut elemens = Vec::with_capacity(N);
-        while let Some(next) = seq.next_element::<T>()? {
-            elemens.push(Some(next));
+        assert!(
+            new_len <= N,
+            "Can't resize LimitedVec to {new_len} items with capacity of {N}"
+        );
+        self.truncate(new_len);
+        for slot in &mut self.0[..new_len] {
+            if slot.is_none() {
+                *slot = Some(T::default());
+            }
         }
-        assert!(N >= elemens.len());
-        elemens.extend((0..(N - elemens.len())).map(|_| None));
-        Ok(LimitedVec(elemens.try_into().ok().unwrap()))
     }
-}
 
-#[cfg(feature = "with_serde")]
-impl<'de, T, const N: usize> Deserialize<'de> for LimitedVec<T, N>
-where
-    T: Deserialize<'de>,
-{
+    /// Removes all items
     #[inline]
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        deserializer.deserialize_seq(LimitedVecVisitor::<T, N> {
-            pd: std::marker::PhantomData,
-        })
+    pub fn clear(&mut self) {
+        self.truncate(0);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::LimitedVec;
+    /// Removes the items in `range` and returns them as an iterator. Items which aren't consumed
+    /// get dropped along with the iterator. Panics if the range is out of bounds.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, N> {
+        let range = self.resolve_range(range);
+        Drain::new(self, range)
+    }
 
-    #[test]
-    fn test_len() {
-        const SIZE: usize = 4;
-        let mut vec: LimitedVec<u8, SIZE> = LimitedVec::new();
-        assert_eq!(vec.len(), 0);
-        assert_eq!(vec.capacity(), SIZE);
+    /// Removes the leading items for which `f` returns `true` and returns them as an iterator
+    pub fn drain_while<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> Drain<'_, T, N> {
+        let count = self.iter().take_while(|i| f(i)).count();
+        self.drain(..count)
+    }
 
-        vec.push(42);
-        assert_eq!(vec.len(), 1);
-        assert_eq!(vec.free(), SIZE - 1);
+    /// Removes the last `n` items, or all if there are less, and returns them in their
+    /// original order
+    pub fn pop_n(&mut self, n: usize) -> LimitedVec<T, N> {
+        let len = self.len();
+        let start = len - n.min(len);
+        let mut popped = Self::default();
+        for (pos, slot) in self.0[start..len].iter_mut().enumerate() {
+            popped.0[pos] = slot.take();
+        }
+        popped
+    }
 
-        vec.push(69);
-        assert_eq!(vec.len(), 2);
-        assert_eq!(vec.free(), SIZE - 2);
+    /// Removes the first `n` items, or all if there are less, and returns them in their original
+    /// order. The remaining items get shifted to the front at once.
+    pub fn pop_front_n(&mut self, n: usize) -> LimitedVec<T, N> {
+        let len = self.len();
+        let n = n.min(len);
+        let mut popped = Self::default();
+        popped.0[..n].swap_with_slice(&mut self.0[..n]);
+        self.0[..len].rotate_left(n);
+        popped
+    }
 
-        assert_eq!(vec.get(0), Some(&42));
+    /// Clones all items of `other` onto the end of the LimitedVec, leaving `other` untouched.
+    /// Panics if the items don't fit.
+    pub fn extend_from_limited<const M: usize>(&mut self, other: &LimitedVec<T, M>)
+    where
+        T: Clone,
+    {
+        self.extend(other.iter().cloned());
+    }
+
+    /// Pushes the items of `iter` until the LimitedVec is full and drops all remaining items, so
+    /// the iterator always gets fully consumed. See also [`saturating_push`].
+    ///
+    /// [`saturating_push`]: LimitedVec::saturating_push
+    pub fn saturating_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.saturating_push(item);
+        }
+    }
+
+    /// Appends `count` clones of `value` to the end of the LimitedVec, eg. to pad it. Panics
+    /// without adding anything if the items don't fit.
+    pub fn repeat_fill(&mut self, value: T, count: usize)
+    where
+        T: Clone,
+    {
+        self.assert_free_at_least(count);
+        self.extend(std::iter::repeat_n(value, count));
+    }
+
+    /// Merges the sorted items of `sorted` into the sorted items in a single pass instead of
+    /// inserting them one by one, putting items of `sorted` after equal items. Panics without
+    /// changing anything if the items don't fit.
+    pub fn merge_insert_all(&mut self, sorted: &[T])
+    where
+        T: Ord + Clone,
+    {
+        self.assert_free_at_least(sorted.len());
+        let (mut pos, mut rest) = (self.len(), sorted);
+        // Merging from the back moves each item only once. The slots stay sorted with a gap in
+        // between, so compacting keeps them sorted even if cloning panics.
+        let guard = CompactOnDrop(self);
+        let slots = &mut guard.0 .0;
+        while let Some((item, head)) = rest.split_last() {
+            let write = pos + rest.len() - 1;
+            if pos > 0 && slots[pos - 1].as_ref() > Some(item) {
+                slots.swap(pos - 1, write);
+                pos -= 1;
+            } else {
+                slots[write] = Some(item.clone());
+                rest = head;
+            }
+        }
+    }
+
+    /// Merges two sorted LimitedVecs into a new sorted LimitedVec. Panics if the items don't fit
+    /// into the capacity `O`.
+    pub fn merge_sorted<const M: usize, const O: usize>(
+        self,
+        other: LimitedVec<T, M>,
+    ) -> LimitedVec<T, O>
+    where
+        T: Ord,
+    {
+        let mut left = self.into_iter().peekable();
+        let mut right = other.into_iter().peekable();
+        let mut merged = LimitedVec::default();
+        loop {
+            let next = match (left.peek(), right.peek()) {
+                (Some(l), Some(r)) if r < l => right.next(),
+                (Some(_), _) => left.next(),
+                (None, _) => right.next(),
+            };
+            match next {
+                Some(item) => merged.push(item),
+                None => return merged,
+            }
+        }
+    }
+
+    /// Returns only the items for which `f` returns `true`, dropping all other items front to back
+    #[inline]
+    pub fn filter<F: FnMut(&T) -> bool>(mut self, f: F) -> LimitedVec<T, N> {
+        self.retain(f);
+        self
+    }
+
+    /// Maps all items with `f`, stopping at the first error and returning it. In that case the
+    /// already mapped and the remaining items get dropped.
+    pub fn try_map<U, E, F: FnMut(T) -> Result<U, E>>(
+        self,
+        mut f: F,
+    ) -> Result<LimitedVec<U, N>, E> {
+        let mut mapped = LimitedVec::default();
+        for (pos, item) in self.into_iter().enumerate() {
+            mapped.0[pos] = Some(f(item)?);
+        }
+        Ok(mapped)
+    }
+
+    /// Returns the leading items for which `f` returns `true`, dropping all other items
+    pub fn take_while<F: FnMut(&T) -> bool>(self, f: F) -> LimitedVec<T, N> {
+        self.into_iter().take_while(f).collect()
+    }
+
+    /// Returns all items after the leading items for which `f` returns `true`, dropping the
+    /// leading items
+    pub fn skip_while<F: FnMut(&T) -> bool>(self, f: F) -> LimitedVec<T, N> {
+        self.into_iter().skip_while(f).collect()
+    }
+
+    /// Alternates the items of both LimitedVecs, starting with `self`, and appends the remaining
+    /// items of the longer one. Panics if the items don't fit into the capacity `O`.
+    pub fn interleave<const M: usize, const O: usize>(
+        self,
+        other: LimitedVec<T, M>,
+    ) -> LimitedVec<T, O> {
+        let mut left = self.into_iter();
+        let mut right = other.into_iter();
+        let mut interleaved = LimitedVec::default();
+        loop {
+            match (left.next(), right.next()) {
+                (None, None) => return interleaved,
+                (l, r) => interleaved.extend(l.into_iter().chain(r)),
+            }
+        }
+    }
+
+    /// Returns the count of items the vector is holding
+    pub fn len(&self) -> usize {
+        debug_assert!(
+            self.is_packed(),
+            "LimitedVec has free slots between its items, call compact() first"
+        );
+        self.0.iter().take_while(|i| i.is_some()).count()
+    }
+
+    /// Returns the count of occupied slots. Unlike `len()` this also counts the items after a free
+    /// slot, which only exist if the slots got modified via `as_mut_array`.
+    #[inline]
+    pub fn count_occupied(&self) -> usize {
+        self.0.iter().filter(|i| i.is_some()).count()
+    }
+
+    /// Returns a bitmask where bit `i` is set if slot `i` is occupied. Fails to compile if `N` is
+    /// greater than 64, see `occupancy_mask_u128` for bigger capacities.
+    pub fn occupancy_mask(&self) -> u64 {
+        const { assert!(N <= 64, "occupancy_mask requires a capacity of at most 64") };
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.is_some())
+            .fold(0, |mask, (pos, _)| mask | 1 << pos)
+    }
+
+    /// Returns a bitmask where bit `i` is set if slot `i` is occupied. Fails to compile if `N` is
+    /// greater than 128.
+    pub fn occupancy_mask_u128(&self) -> u128 {
+        const {
+            assert!(
+                N <= 128,
+                "occupancy_mask_u128 requires a capacity of at most 128"
+            )
+        };
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.is_some())
+            .fold(0, |mask, (pos, _)| mask | 1 << pos)
+    }
+
+    /// Returns `true` if there is no item pushed onto the LimitedVec
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the name of the LimitedVec type including its item type and capacity, eg.
+    /// `LimitedVec<u8, 16>`, to identify it in logs
+    pub fn describe() -> String {
+        format!("LimitedVec<{}, {N}>", std::any::type_name::<T>())
+    }
+
+    /// Returns the amount of items the LimitedVec can hold
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the amount of free slots which can be used to push more items
+    #[inline]
+    pub fn free(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Panics if there are less than `n` free slots left, eg. before pushing `n` items
+    #[inline]
+    pub fn assert_free_at_least(&self, n: usize) {
+        let free = self.free();
+        assert!(
+            free >= n,
+            "LimitedVec needs {n} free slots but only has {free}"
+        );
+    }
+
+    /// Fails to compile if the capacity is less than `NEEDED`, eg. to statically ensure a known
+    /// amount of items fits. Calls in a `const` item are checked by `cargo check` already, other
+    /// calls only when the code gets built.
+    #[inline]
+    pub const fn assert_fits<const NEEDED: usize>() {
+        const { assert!(NEEDED <= N, "LimitedVec capacity is too small") };
+    }
+
+    /// Returns `true` if there is no free slot left
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.free() == 0
+    }
+
+    /// Gets an item by its index
+    #[inline]
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        if pos >= self.len() {
+            return None;
+        }
+        Some(&self[pos])
+    }
+
+    /// Clones all items one by one, returning the first error that occurs
+    pub fn try_clone(&self) -> Result<Self, T::Error>
+    where
+        T: TryClone,
+    {
+        let mut lvec = Self::default();
+        for (pos, i) in self.iter().enumerate() {
+            lvec.0[pos] = Some(i.try_clone()?);
+        }
+        Ok(lvec)
+    }
+
+    /// Gets an item by its index, wrapping around at the end of the LimitedVec. Returns None if
+    /// the LimitedVec is empty.
+    #[inline]
+    pub fn cyclic_get(&self, index: usize) -> Option<&T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        self.get(index % len)
+    }
+
+    /// Returns the index of the first item equal to `x`, eg. to `remove` it afterwards
+    #[inline]
+    pub fn index_of(&self, x: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter().position(|i| i == x)
+    }
+
+    /// Returns the index of the last item equal to `x`
+    #[inline]
+    pub fn last_index_of(&self, x: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter().rposition(|i| i == x)
+    }
+
+    /// Returns an iterator over the indices of all items for which `f` returns `true`
+    #[inline]
+    pub fn positions<'a, F: FnMut(&T) -> bool + 'a>(
+        &'a self,
+        mut f: F,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.iter()
+            .enumerate()
+            .filter_map(move |(pos, i)| f(i).then_some(pos))
+    }
+
+    /// Sorts the items by the key `f` returns, calling `f` only once per item. The sort is stable
+    /// and like `slice::sort_by_cached_key` worth it if computing the key is expensive.
+    pub fn sort_by_cached_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        let len = self.len();
+        self.0[..len].sort_by_cached_key(|i| f(i.as_ref().unwrap()));
+    }
+
+    /// Returns the amount of items less than `x`. The items have to be sorted.
+    #[inline]
+    pub fn rank(&self, x: &T) -> usize
+    where
+        T: Ord,
+    {
+        self.partition_point(|i| i < x)
+    }
+
+    /// Returns `true` if there is an item equal to `x` using a binary search. The items have to be
+    /// sorted, otherwise the result is unspecified. Debug builds assert that they are.
+    pub fn contains_sorted(&self, x: &T) -> bool
+    where
+        T: Ord,
+    {
+        debug_assert!(
+            self.iter().is_sorted(),
+            "contains_sorted requires the items to be sorted"
+        );
+        let pos = self.rank(x);
+        self.get(pos) == Some(x)
+    }
+
+    /// Returns the range of indices of all items equal to `x`, which is empty at the index `x`
+    /// would be inserted at if there is no such item. The items have to be sorted.
+    pub fn equal_range(&self, x: &T) -> Range<usize>
+    where
+        T: Ord,
+    {
+        self.partition_point(|i| i < x)..self.partition_point(|i| i <= x)
+    }
+
+    /// Returns the `k`-th smallest item. The items have to be sorted.
+    #[inline]
+    pub fn select(&self, k: usize) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.get(k)
+    }
+
+    /// Returns a clone of the item at `pos` or `T::default()` if there is no such item
+    #[inline]
+    pub fn get_or_default(&self, pos: usize) -> T
+    where
+        T: Default + Clone,
+    {
+        self.get(pos).cloned().unwrap_or_default()
+    }
+
+    /// Gets the item `n` positions before the last one, so `get_back(0)` is the last item
+    #[inline]
+    pub fn get_back(&self, n: usize) -> Option<&T> {
+        let pos = self.last_idx()?.checked_sub(n)?;
+        self.get(pos)
+    }
+
+    /// Returns the first item of the LimitedVec or None if its empty.
+    #[inline]
+    pub fn first(&self) -> Option<&T> {
+        self.0.first()?.as_ref()
+    }
+
+    /// Returns the first item of the LimitedVec or None if its empty.
+    #[inline]
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.0.first_mut()?.as_mut()
+    }
+
+    /// Returns the first item of the LimitedVec or an error if its empty.
+    #[inline]
+    pub fn try_first(&self) -> Result<&T, EmptyError> {
+        self.first().ok_or(EmptyError)
+    }
+
+    /// Returns the last item of the LimitedVec or an error if its empty.
+    #[inline]
+    pub fn try_last(&self) -> Result<&T, EmptyError> {
+        self.last().ok_or(EmptyError)
+    }
+
+    /// Returns the last item of the LimitedVec or None if its empty.
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        let last_pos = self.last_idx()?;
+        self.0[last_pos].as_mut()
+    }
+
+    /// Returns the last item of the LimitedVec or None if its empty.
+    pub fn last(&self) -> Option<&T> {
+        let last_pos = self.last_idx()?;
+        self.0[last_pos].as_ref()
+    }
+
+    /// Returns the index of the last item with a value or None if the LimitedVec is empty.
+    #[inline]
+    pub fn last_idx(&self) -> Option<usize> {
+        self.len().checked_sub(1)
+    }
+
+    /// Keeps only the items for which `f` returns `true`. `f` is called once per item in order
+    /// and removed items are dropped right away, so they're dropped front to back.
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.retain_range(0..self.len(), f);
+    }
+
+    /// Keeps only the items for which `f` returns `true` like `retain`, but calls `f` on the items
+    /// from the last to the first one. Removed items get dropped back to front, the kept items
+    /// keep their original order.
+    pub fn retain_rev<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let len = self.len();
+        let guard = CompactOnDrop(self);
+        for slot in guard.0 .0[..len].iter_mut().rev() {
+            if !slot.as_ref().is_some_and(&mut f) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Keeps only the items for which `f` returns `true` and removes consecutive duplicates in a
+    /// single pass. Filtering comes first, so an item is only compared with the last kept item and
+    /// duplicates separated by removed items get merged too.
+    pub fn retain_dedup<F: FnMut(&T) -> bool>(&mut self, mut f: F)
+    where
+        T: PartialEq,
+    {
+        let len = self.len();
+        let guard = CompactOnDrop(self);
+        let slots = &mut guard.0 .0;
+        let mut last_kept = None;
+        for pos in 0..len {
+            let keep = slots[pos].as_ref().is_some_and(&mut f)
+                && last_kept.is_none_or(|last: usize| slots[last] != slots[pos]);
+            if keep {
+                last_kept = Some(pos);
+            } else {
+                slots[pos] = None;
+            }
+        }
+    }
+
+    /// Keeps or removes each item depending on the `RetainAction` returned by `f`. Once `f`
+    /// returns `StopKeepingRest`, the scan stops and all remaining items are kept.
+    pub fn retain_controlled<F: FnMut(&T) -> RetainAction>(&mut self, mut f: F) {
+        let len = self.len();
+        let guard = CompactOnDrop(self);
+        for slot in &mut guard.0 .0[..len] {
+            match slot.as_ref().map(&mut f) {
+                Some(RetainAction::Keep) => {}
+                Some(RetainAction::Remove) => *slot = None,
+                Some(RetainAction::StopKeepingRest) | None => break,
+            }
+        }
+    }
+
+    /// Keeps only the items for which `f` returns `true` and removes the items at the same indices
+    /// from `other`, so both stay aligned. Panics if both don't have the same length.
+    pub fn retain_with<U, F: FnMut(&T) -> bool, const M: usize>(
+        &mut self,
+        other: &mut LimitedVec<U, M>,
+        mut f: F,
+    ) {
+        let len = self.len();
+        let other_len = other.len();
+        assert_eq!(
+            len, other_len,
+            "Can't retain LimitedVecs of different lengths {len} and {other_len}"
+        );
+        let guard = CompactOnDrop(self);
+        let other_guard = CompactOnDrop(other);
+        let slots = guard.0 .0[..len].iter_mut();
+        for (slot, other_slot) in slots.zip(&mut other_guard.0 .0[..len]) {
+            if !slot.as_ref().is_some_and(&mut f) {
+                *slot = None;
+                *other_slot = None;
+            }
+        }
+    }
+
+    /// Keeps only the items for which `f` returns `true` and returns whether any item got removed
+    #[inline]
+    pub fn retain_changed<F: FnMut(&T) -> bool>(&mut self, f: F) -> bool {
+        let len = self.len();
+        self.retain(f);
+        self.len() != len
+    }
+
+    /// Keeps only the items for which `f` returns `true` and returns how many items got kept and
+    /// removed
+    pub fn retain_stats<F: FnMut(&T) -> bool>(&mut self, f: F) -> RetainStats {
+        let len = self.len();
+        self.retain(f);
+        let kept = self.len();
+        RetainStats {
+            kept,
+            removed: len - kept,
+        }
+    }
+
+    /// Keeps only the items for which `f` returns `true` and returns the amount of kept items
+    #[inline]
+    pub fn retain_returning_len<F: FnMut(&T) -> bool>(&mut self, f: F) -> usize {
+        self.retain(f);
+        self.len()
+    }
+
+    /// Keeps only the items for which `f` returns `Ok(true)`. Stops at the first error and returns
+    /// it, in which case the items before the failed one are filtered already while the failed
+    /// item and all following items are kept.
+    pub fn try_retain<E, F: FnMut(&T) -> Result<bool, E>>(&mut self, mut f: F) -> Result<(), E> {
+        let mut result = Ok(());
+        self.retain(|i| {
+            if result.is_err() {
+                return true;
+            }
+            f(i).unwrap_or_else(|e| {
+                result = Err(e);
+                true
+            })
+        });
+        result
+    }
+
+    /// Keeps only the items within `range` for which `f` returns `true`. Items outside of `range`
+    /// are kept untouched. Removed items are dropped front to back. Panics if the range is out of
+    /// bounds. If `f` or dropping an item panics, the items processed so far stay filtered and all
+    /// other items are kept.
+    pub fn retain_range<F: FnMut(&T) -> bool>(&mut self, range: Range<usize>, mut f: F) {
+        let Range { start, end } = self.resolve_range(range);
+        // Removed items leave free slots behind which get closed even if `f` panics
+        let guard = CompactOnDrop(self);
+        for slot in &mut guard.0 .0[start..end] {
+            if !slot.as_ref().is_some_and(&mut f) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Keeps only the items for which `f` returns `true` and returns the removed items in a new
+    /// LimitedVec. Both keep the original order of their items.
+    pub fn retain_split<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> LimitedVec<T, N> {
+        let mut removed = Self::default();
+        let len = self.len();
+        let guard = CompactOnDrop(self);
+        for slot in &mut guard.0 .0[..len] {
+            if slot.as_ref().is_some_and(&mut f) {
+                continue;
+            }
+            if let Some(item) = slot.take() {
+                removed.push(item);
+            }
+        }
+        removed
+    }
+
+    /// Moves all items for which `f` returns `true` onto the end of `dest`, keeping the order of
+    /// the items in both. Panics if `dest` gets full, in which case the item that doesn't fit and
+    /// all following items stay in the LimitedVec.
+    pub fn drain_filter_into<F: FnMut(&T) -> bool>(
+        &mut self,
+        dest: &mut LimitedVec<T, N>,
+        mut f: F,
+    ) {
+        let len = self.len();
+        let guard = CompactOnDrop(self);
+        for slot in &mut guard.0 .0[..len] {
+            if !slot.as_ref().is_some_and(&mut f) {
+                continue;
+            }
+            if let Err(err) = dest.try_push(slot.take().unwrap()) {
+                *slot = Some(err.into_inner());
+                panic!("Trying to push more elements than LimitedVec can hold");
+            }
+        }
+    }
+
+    /// Returns references to all occupied items. The slots aren't stored as a contiguous `[T]`
+    /// so this can't return a slice.
+    #[inline]
+    pub fn occupied(&self) -> Vec<&T> {
+        self.iter().collect()
+    }
+
+    /// Returns an iterator over the items. The iterator borrows the LimitedVec, so it can't be
+    /// truncated or otherwise modified until the iterator is dropped.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator over the indices of all occupied slots. Free slots are skipped, so this
+    /// also works if the slots aren't compacted.
+    #[inline]
+    pub fn indices(&self) -> IndexIter<'_, T, N> {
+        IndexIter::new(self)
+    }
+
+    /// Returns an iterator which can look ahead any amount of items without consuming them
+    #[inline]
+    pub fn peekable(&self) -> PeekBuffer<'_, T, N> {
+        PeekBuffer::new(self.iter())
+    }
+
+    /// Returns an iterator yielding `(index, len, item)` for progress reporting
+    #[inline]
+    pub fn iter_progress(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        let len = self.len();
+        self.iter().enumerate().map(move |(pos, i)| (pos, len, i))
+    }
+
+    /// Returns an iterator over mutable references of all items. Free slots are skipped, so all
+    /// items are visited even if the slots aren't compacted.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, N> {
+        IterMut::new(&mut self.0)
+    }
+
+    /// Splits the items at `mid` into two disjoint mutable iterators over `0..mid` and
+    /// `mid..len`. Panics if `mid > len`.
+    pub fn split_at_mut(&mut self, mid: usize) -> (IterMut<'_, T, N>, IterMut<'_, T, N>) {
+        let len = self.len();
+        assert!(
+            mid <= len,
+            "Split index {mid} out of bounds with length of {len}"
+        );
+        let (left, right) = self.0[..len].split_at_mut(mid);
+        (IterMut::new(left), IterMut::new(right))
+    }
+
+    /// Returns references to the first `M` items and an iterator over the remaining items, or
+    /// None if there are less than `M` items. The items aren't contiguous so the chunk is an
+    /// array of references instead of a reference to an array.
+    pub fn split_first_chunk<const M: usize>(&self) -> Option<([&T; M], Iter<'_, T, N>)> {
+        let len = self.len();
+        if len < M {
+            return None;
+        }
+        let chunk = std::array::from_fn(|pos| &self[pos]);
+        Some((chunk, Iter::with_range(self, M..len)))
+    }
+
+    /// Returns an iterator over the leading items and references to the last `M` items, or None
+    /// if there are less than `M` items
+    pub fn split_last_chunk<const M: usize>(&self) -> Option<(Iter<'_, T, N>, [&T; M])> {
+        let len = self.len();
+        let start = len.checked_sub(M)?;
+        let chunk = std::array::from_fn(|pos| &self[start + pos]);
+        Some((Iter::with_range(self, 0..start), chunk))
+    }
+
+    /// Folds all overlapping windows of `W` consecutive items into an accumulator, starting with
+    /// the first window. The items aren't contiguous so each window is an array of references
+    /// built on the stack. Fails to compile if `W` is 0.
+    pub fn windows_fold<const W: usize, B, F: FnMut(B, &[&T; W]) -> B>(
+        &self,
+        init: B,
+        mut f: F,
+    ) -> B {
+        const { assert!(W != 0, "Window size must be non-zero") };
+        let len = self.len();
+        let mut acc = init;
+        for start in 0..(len + 1).saturating_sub(W) {
+            let window = std::array::from_fn(|pos| &self[start + pos]);
+            acc = f(acc, &window);
+        }
+        acc
+    }
+
+    /// Folds all items into an accumulator, starting with the last item
+    #[inline]
+    pub fn rfold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().rfold(init, f)
+    }
+
+    /// Returns a LimitedVec holding the running accumulation of `f` for each item, eg. prefix sums
+    pub fn prefix_scan<B: Clone, F: FnMut(&B, &T) -> B>(
+        &self,
+        init: B,
+        mut f: F,
+    ) -> LimitedVec<B, N> {
+        let mut acc = init;
+        let mut scanned = LimitedVec::default();
+        for (pos, i) in self.iter().enumerate() {
+            acc = f(&acc, i);
+            scanned.0[pos] = Some(acc.clone());
+        }
+        scanned
+    }
+
+    /// Returns `true` if the items read the same forwards and backwards
+    pub fn eq_reversed(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        let Some(mut back) = self.last_idx() else {
+            return true;
+        };
+        let mut front = 0;
+        while front < back {
+            if self[front] != self[back] {
+                return false;
+            }
+            front += 1;
+            back -= 1;
+        }
+        true
+    }
+
+    /// Returns an iterator over read-only views of all overlapping windows of `size` consecutive
+    /// items. Panics if `size` is 0.
+    #[inline]
+    pub fn windows(&self, size: usize) -> Windows<'_, T, N> {
+        assert!(size != 0, "Window size must be non-zero");
+        Windows::new(self, size)
+    }
+
+    /// Returns an iterator over read-only views of non-overlapping chunks of `size` items. The
+    /// last chunk may be shorter if `size` doesn't divide the length. Panics if `size` is 0.
+    #[inline]
+    pub fn chunks(&self, size: usize) -> Chunks<'_, T, N> {
+        assert!(size != 0, "Chunk size must be non-zero");
+        Chunks::new(self, size)
+    }
+
+    /// Returns an iterator over non-overlapping chunks of `size` mutable items. The last chunk
+    /// may be shorter if `size` doesn't divide the length. Panics if `size` is 0.
+    #[inline]
+    pub fn chunks_mut(&mut self, size: usize) -> ChunksMut<'_, T, N> {
+        assert!(size != 0, "Chunk size must be non-zero");
+        let len = self.len();
+        ChunksMut::new(&mut self.0[..len], size)
+    }
+
+    /// Consumes the LimitedVec and returns an iterator over owned chunks of `M` items. The last
+    /// chunk may be shorter if `M` doesn't divide the length. Fails to compile if `M` is 0.
+    pub fn into_chunks<const M: usize>(self) -> impl Iterator<Item = LimitedVec<T, M>> {
+        const { assert!(M != 0, "Chunk size must be non-zero") };
+        let mut items = self.into_iter();
+        std::iter::from_fn(move || {
+            let chunk: LimitedVec<T, M> = items.by_ref().take(M).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        })
+    }
+
+    /// Converts `range` into a `Range` over the items. Panics if it's out of bounds.
+    fn resolve_range<R: RangeBounds<usize>>(&self, range: R) -> Range<usize> {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.checked_add(1).unwrap_or_else(|| {
+                panic!("Range starting after {s} out of bounds with length of {len}")
+            }),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.checked_add(1).unwrap_or_else(|| {
+                panic!("Range {start}..={e} out of bounds with length of {len}")
+            }),
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end && end <= len,
+            "Range {start}..{end} out of bounds with length of {len}"
+        );
+        start..end
+    }
+
+    /// Returns the index of the first item for which `pred` returns `false`, assuming all items
+    /// for which it returns `true` come first
+    fn partition_point<P: FnMut(&T) -> bool>(&self, mut pred: P) -> usize {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(&self[mid]) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Returns `true` if no occupied slot follows a free one
+    #[inline]
+    fn is_packed(&self) -> bool {
+        self.0
+            .iter()
+            .skip_while(|i| i.is_some())
+            .all(|i| i.is_none())
+    }
+
+    /// Creates a LimitedVec from its slots without checking that they are packed, allowing tests
+    /// to set up free slots between items
+    #[cfg(test)]
+    pub(crate) fn from_slots(slots: [Option<T>; N]) -> Self {
+        LimitedVec(slots)
+    }
+
+    /// Returns an iterator over chunks of exactly `size` items, starting at the end. The leading
+    /// items which don't make up a complete chunk are available via `remainder()`.
+    /// Panics if `size` is 0.
+    #[inline]
+    pub fn rchunks_exact(&self, size: usize) -> RChunksExact<'_, T, N> {
+        assert!(size != 0, "Chunk size must be non-zero");
+        RChunksExact::new(self, size)
+    }
+
+    /// Returns the next empty allocated item
+    #[inline]
+    fn next_mut(&mut self) -> Option<&mut Option<T>> {
+        self.0.iter_mut().find(|i| i.is_none())
+    }
+}
+
+impl<T, const N: usize> sealed::Sealed for LimitedVec<T, N> {}
+
+impl<T, const N: usize> BoundedCapacity for LimitedVec<T, N> {
+    const CAPACITY: usize = N;
+
+    #[inline]
+    fn current_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Compacts the slots of a LimitedVec when dropped, so a panicking closure can't leave free slots
+/// between its items
+struct CompactOnDrop<'a, T, const N: usize>(&'a mut LimitedVec<T, N>);
+
+impl<T, const N: usize> Drop for CompactOnDrop<'_, T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.compact();
+    }
+}
+
+impl<T, const N: usize> Index<usize> for LimitedVec<T, N> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        match &self.0[index] {
+            Some(e) => e,
+            None => {
+                let cap = self.capacity();
+                panic!("Index {index} out of bounds with capacity of {cap}",)
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> IntoIterator for LimitedVec<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
+
+impl<T, const N: usize> From<Vec<T>> for LimitedVec<T, N> {
+    fn from(values: Vec<T>) -> Self {
+        if values.len() > N {
+            panic!("Vec is larger than LimitedVec's capacity");
+        }
+        let free = N - values.len();
+        let free_iter = (0..free).map(|_| None);
+        let d: [Option<T>; N] = values
+            .into_iter()
+            .map(|i| Some(i))
+            .chain(free_iter)
+            .collect::<Vec<_>>()
+            .try_into()
+            .ok()
+            .unwrap();
+        LimitedVec(d)
+    }
+}
+
+impl<T, const N: usize> From<LimitedVec<T, N>> for Vec<T> {
+    #[inline]
+    fn from(lvec: LimitedVec<T, N>) -> Self {
+        lvec.into_vec()
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for LimitedVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut buf = Vec::with_capacity(N);
+        for (index, i) in iter.into_iter().enumerate() {
+            if index >= N {
+                panic!(
+                    "Can't collect more elements into LimitedVec than capacity ({N}): \
+                    element at index {index} doesn't fit"
+                );
+            }
+            buf.push(Some(i));
+        }
+        if buf.len() < N {
+            let free_iter = (0..(N - buf.len())).map(|_| None);
+            buf.extend(free_iter);
+        }
+        LimitedVec(buf.try_into().ok().unwrap())
+    }
+}
+
+impl<T, const N: usize> Extend<T> for LimitedVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start_idx = self.last_idx().map(|i| i + 1).unwrap_or(0);
+        for (pos, i) in iter.into_iter().enumerate() {
+            let dst_idx = start_idx + pos;
+            if dst_idx >= N {
+                panic!("Can't push more elements than LimitedVec capacity!");
+            }
+            self.0[dst_idx] = Some(i);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, const N: usize> Extend<&'a T> for LimitedVec<T, N> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<LimitedVec<T, M>>
+    for LimitedVec<T, N>
+{
+    /// Compares the items, independent of the capacities
+    #[inline]
+    fn eq(&self, other: &LimitedVec<T, M>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for LimitedVec<T, N> {}
+
+impl<T: Hash, const N: usize> Hash for LimitedVec<T, N> {
+    /// Hashes the items in their logical order, independent of how they're stored
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for i in self.iter() {
+            i.hash(state);
+        }
+    }
+}
+
+impl<T: std::fmt::Debug, const N: usize> std::fmt::Debug for LimitedVec<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "with_serde")]
+impl<T, const N: usize> Serialize for LimitedVec<T, N>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut list = serializer.serialize_seq(Some(self.len()))?;
+        for i in self.iter() {
+            list.serialize_element(i)?;
+        }
+        list.end()
+    }
+}
+
+#[cfg(feature = "with_serde")]
+pub struct LimitedVecVisitor<T, const N: usize> {
+    pd: std::marker::PhantomData<T>,
+    max: usize,
+}
+
+#[cfg(feature = "with_serde")]
+impl<'de, T, const N: usize> Visitor<'de> for LimitedVecVisitor<T, N>
+where
+    T: Deserialize<'de>,
+{
+    type Value = LimitedVec<T, N>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a sequence with at most {} elements", self.max)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut elemens = Vec::with_capacity(N);
+        while let Some(next) = seq.next_element::<T>()? {
+            if elemens.len() >= self.max {
+                return Err(serde::de::Error::invalid_length(elemens.len() + 1, &self));
+            }
+            elemens.push(Some(next));
+        }
+        elemens.extend((0..(N - elemens.len())).map(|_| None));
+        Ok(LimitedVec(elemens.try_into().ok().unwrap()))
+    }
+}
+
+#[cfg(feature = "with_serde")]
+impl<'de, T, const N: usize> Deserialize<'de> for LimitedVec<T, N>
+where
+    T: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_bounded(deserializer, N)
+    }
+}
+
+#[cfg(feature = "with_serde")]
+impl<T, const N: usize> LimitedVec<T, N> {
+    /// Deserializes a LimitedVec but fails if there are more than `max` elements, even if they
+    /// would fit into the capacity
+    pub fn deserialize_bounded<'de, D>(deserializer: D, max: usize) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        deserializer.deserialize_seq(LimitedVecVisitor::<T, N> {
+            pd: std::marker::PhantomData,
+            max: max.min(N),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LimitedVec;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Records its id when dropped to check that items get dropped exactly once
+    #[derive(Debug)]
+    struct DropCounter {
+        id: usize,
+        dropped: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.dropped.borrow_mut().push(self.id);
+        }
+    }
+
+    /// Panics when the wrapped DropCounter with id 2 gets dropped
+    struct PanicOnDrop(DropCounter);
+
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            if self.0.id == 2 {
+                panic!("Dropping item 2");
+            }
+        }
+    }
+
+    /// Returns a LimitedVec with `count` DropCounters and the ids which have been dropped
+    fn drop_counters<const N: usize>(
+        count: usize,
+    ) -> (LimitedVec<DropCounter, N>, Rc<RefCell<Vec<usize>>>) {
+        let dropped = Rc::new(RefCell::new(Vec::new()));
+        let lvec = (0..count)
+            .map(|id| DropCounter {
+                id,
+                dropped: dropped.clone(),
+            })
+            .collect();
+        (lvec, dropped)
+    }
+
+    /// Drops `lvec` and asserts that all `count` items have been dropped exactly once
+    fn assert_all_dropped_once<const N: usize>(
+        lvec: LimitedVec<DropCounter, N>,
+        dropped: &Rc<RefCell<Vec<usize>>>,
+        count: usize,
+    ) {
+        drop(lvec);
+        let mut dropped = dropped.borrow().clone();
+        dropped.sort_unstable();
+        assert_eq!(dropped, (0..count).collect::<Vec<_>>());
+    }
+
+    fn ids<const N: usize>(lvec: &LimitedVec<DropCounter, N>) -> Vec<usize> {
+        lvec.iter().map(|i| i.id).collect()
+    }
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_len() {
+        const SIZE: usize = 4;
+        let mut vec: LimitedVec<u8, SIZE> = LimitedVec::new();
+        assert_eq!(vec.len(), 0);
+        assert_eq!(vec.capacity(), SIZE);
+
+        vec.push(42);
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec.free(), SIZE - 1);
+
+        vec.push(69);
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec.free(), SIZE - 2);
+
+        assert_eq!(vec.get(0), Some(&42));
         assert_eq!(vec[0], 42);
 
-        assert_eq!(vec.pop(), Some(69));
-        assert_eq!(vec.len(), 1);
-        assert_eq!(vec.free(), SIZE - 1);
+        assert_eq!(vec.pop(), Some(69));
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec.free(), SIZE - 1);
+
+        assert_eq!(vec.pop(), Some(42));
+        assert_eq!(vec.len(), 0);
+        assert_eq!(vec.free(), SIZE);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        const SIZE: usize = 10;
+        let src_vec = (0..7).collect::<Vec<usize>>();
+        let lvec = LimitedVec::<_, SIZE>::from(src_vec.clone());
+        assert_eq!(
+            lvec.iter().collect::<Vec<_>>(),
+            src_vec.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_from_iter() {
+        const SIZE: usize = 10;
+        let src_vec = (0..7).collect::<Vec<usize>>();
+        let lvec = (0..7).collect::<LimitedVec<usize, SIZE>>();
+        assert_eq!(
+            lvec.iter().collect::<Vec<_>>(),
+            src_vec.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_extend() {
+        const SIZE: usize = 50;
+        let src_vec = (0..7).collect::<Vec<usize>>();
+        let ext_data = (7..25).collect::<Vec<_>>();
+
+        let mut lvec = LimitedVec::<_, SIZE>::from_iter(src_vec.iter().copied());
+        lvec.extend(ext_data.iter().copied());
+        let expected = src_vec
+            .iter()
+            .copied()
+            .chain(ext_data.iter().copied())
+            .collect::<Vec<_>>();
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), expected);
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_bincode() {
+        let iter = (0..10).map(|i| format!("Number: {i}"));
+        let lvec = LimitedVec::<String, 14>::from_iter(iter);
+
+        let encoded = bincode::serialize(&lvec).unwrap();
+        let decoded: LimitedVec<String, 14> = bincode::deserialize(&encoded).unwrap();
+
+        assert_eq!(lvec, decoded);
+    }
+
+    #[test]
+    fn test_to_chunks() {
+        let lvec = (1..=7).collect::<LimitedVec<u8, 8>>();
+        let (chunks, remainder) = lvec.to_chunks::<2>();
+        assert_eq!(chunks, vec![[1, 2], [3, 4], [5, 6]]);
+        assert_eq!(remainder, vec![7]);
+    }
+
+    #[test]
+    fn test_from_iter_exact() {
+        use super::error::FromIterError;
+
+        let lvec = LimitedVec::<usize, 4>::from_iter_exact(0..4).unwrap();
+        assert!(lvec.is_full());
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+        let under = LimitedVec::<usize, 4>::from_iter_exact(0..3);
+        assert_eq!(under, Err(FromIterError::NotEnough(3)));
+
+        let over = LimitedVec::<usize, 4>::from_iter_exact(0..5);
+        assert_eq!(over, Err(FromIterError::TooMany));
+    }
+
+    #[test]
+    fn test_get_or_default() {
+        let lvec = LimitedVec::<i32, 4>::from(vec![7, 8]);
+        assert_eq!(lvec.get_or_default(1), 8);
+        assert_eq!(lvec.get_or_default(3), 0);
+    }
+
+    #[test]
+    fn test_chunks_mut() {
+        let mut lvec = (1..=5).collect::<LimitedVec<u32, 8>>();
+        for chunk in lvec.chunks_mut(2) {
+            for i in chunk {
+                *i *= 2;
+            }
+        }
+        assert_eq!(
+            lvec.iter().copied().collect::<Vec<_>>(),
+            vec![2, 4, 6, 8, 10]
+        );
+        assert_eq!(lvec.chunks_mut(2).count(), 3);
+    }
+
+    #[test]
+    fn test_split_at_mut() {
+        let mut lvec = (1..=5).collect::<LimitedVec<u32, 8>>();
+        let (left, right) = lvec.split_at_mut(2);
+        for (l, r) in left.zip(right) {
+            *l += 10;
+            *r *= 100;
+        }
+        assert_eq!(
+            lvec.iter().copied().collect::<Vec<_>>(),
+            vec![11, 12, 300, 400, 5]
+        );
+
+        let (left, right) = lvec.split_at_mut(5);
+        assert_eq!(left.count(), 5);
+        assert_eq!(right.count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_at_mut_out_of_bounds() {
+        let mut lvec = (1..=5).collect::<LimitedVec<u32, 8>>();
+        let _ = lvec.split_at_mut(6);
+    }
+
+    #[test]
+    fn test_retain_split() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 3, 4]);
+        let removed = lvec.retain_split(|i| i % 2 == 0);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(removed.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(lvec.free(), 4);
+        assert_eq!(removed.free(), 4);
+    }
+
+    #[test]
+    fn test_cyclic_get() {
+        let lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 3]);
+        assert_eq!(lvec.cyclic_get(7), Some(&2));
+        assert_eq!(lvec.cyclic_get(2), Some(&3));
+        assert_eq!(LimitedVec::<u8, 6>::new().cyclic_get(7), None);
+    }
+
+    #[test]
+    fn test_extend_ref() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![0]);
+        lvec.extend([1, 2, 3].iter());
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_try_clone() {
+        use super::TryClone;
+
+        #[derive(Debug, PartialEq)]
+        struct Handle(u8);
+
+        impl TryClone for Handle {
+            type Error = u8;
+
+            fn try_clone(&self) -> Result<Self, Self::Error> {
+                if self.0 == 3 {
+                    return Err(self.0);
+                }
+                Ok(Handle(self.0))
+            }
+        }
+
+        let lvec = (1..=2).map(Handle).collect::<LimitedVec<_, 4>>();
+        assert_eq!(lvec.try_clone(), Ok(lvec));
+
+        let lvec = (1..=4).map(Handle).collect::<LimitedVec<_, 4>>();
+        assert_eq!(lvec.try_clone(), Err(3));
+    }
+
+    #[test]
+    fn test_iter_remaining() {
+        let lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 3, 4]);
+        let mut iter = lvec.iter();
+        assert_eq!(iter.remaining(), 4);
+        iter.next();
+        iter.next();
+        assert_eq!(iter.remaining(), 2);
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.remaining(), 0);
+    }
+
+    #[test]
+    fn test_extend_from_limited() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![1, 2]);
+        let other = LimitedVec::<u8, 2>::from(vec![3, 4]);
+        lvec.extend_from_limited(&other);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+    }
+
+    #[test]
+    fn test_pop_n() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 3, 4]);
+        let popped = lvec.pop_n(2);
+        assert_eq!(popped.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        let popped = lvec.pop_n(5);
+        assert_eq!(popped.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert!(lvec.is_empty());
+    }
+
+    #[test]
+    fn test_rfold() {
+        let lvec = LimitedVec::<char, 6>::from(vec!['a', 'b', 'c']);
+        let s = lvec.rfold(String::new(), |mut s, c| {
+            s.push(*c);
+            s
+        });
+        assert_eq!(s, "cba");
+
+        let mut iter = lvec.iter();
+        assert_eq!(iter.next_back(), Some(&'c'));
+        assert_eq!(iter.next(), Some(&'a'));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(&'b'));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_eq_reversed() {
+        assert!(LimitedVec::<u8, 4>::from(vec![1, 2, 1]).eq_reversed());
+        assert!(LimitedVec::<u8, 4>::from(vec![1, 2, 2, 1]).eq_reversed());
+        assert!(!LimitedVec::<u8, 4>::from(vec![1, 2, 3]).eq_reversed());
+        assert!(LimitedVec::<u8, 4>::new().eq_reversed());
+    }
+
+    #[test]
+    fn test_prefix_scan() {
+        let lvec = LimitedVec::<u32, 4>::from(vec![1, 2, 3]);
+        let sums = lvec.prefix_scan(0, |acc, i| acc + i);
+        assert_eq!(sums.iter().copied().collect::<Vec<_>>(), vec![1, 3, 6]);
+        assert_eq!(sums.len(), lvec.len());
+    }
+
+    #[test]
+    fn test_drop_clear() {
+        let (mut lvec, dropped) = drop_counters::<6>(4);
+        lvec.clear();
+        assert!(lvec.is_empty());
+        assert_eq!(*dropped.borrow(), vec![0, 1, 2, 3]);
+        assert_all_dropped_once(lvec, &dropped, 4);
+    }
+
+    #[test]
+    fn test_drop_truncate() {
+        let (mut lvec, dropped) = drop_counters::<6>(5);
+        lvec.truncate(2);
+        assert_eq!(ids(&lvec), vec![0, 1]);
+        assert_eq!(*dropped.borrow(), vec![2, 3, 4]);
+        lvec.truncate(3);
+        assert_eq!(dropped.borrow().len(), 3);
+        assert_all_dropped_once(lvec, &dropped, 5);
+    }
+
+    #[test]
+    fn test_drop_retain() {
+        let (mut lvec, dropped) = drop_counters::<6>(5);
+        lvec.retain(|i| i.id % 2 == 0);
+        assert_eq!(ids(&lvec), vec![0, 2, 4]);
+        assert_eq!(*dropped.borrow(), vec![1, 3]);
+        assert_all_dropped_once(lvec, &dropped, 5);
+    }
+
+    #[test]
+    fn test_drop_remove() {
+        let (mut lvec, dropped) = drop_counters::<6>(4);
+        let removed = lvec.remove(1);
+        assert_eq!(removed.id, 1);
+        assert_eq!(ids(&lvec), vec![0, 2, 3]);
+        assert!(dropped.borrow().is_empty());
+        drop(removed);
+        assert_eq!(*dropped.borrow(), vec![1]);
+        assert_all_dropped_once(lvec, &dropped, 4);
+    }
+
+    #[test]
+    fn test_drop_drain() {
+        let (mut lvec, dropped) = drop_counters::<6>(5);
+        let mut drain = lvec.drain(1..4);
+        let first = drain.next().unwrap();
+        assert_eq!(first.id, 1);
+        // The unconsumed items 2 and 3 get dropped with the iterator
+        drop(drain);
+        assert_eq!(*dropped.borrow(), vec![2, 3]);
+        assert_eq!(ids(&lvec), vec![0, 4]);
+        assert_eq!(lvec.free(), 4);
+        drop(first);
+        assert_eq!(*dropped.borrow(), vec![2, 3, 1]);
+        assert_all_dropped_once(lvec, &dropped, 5);
+    }
+
+    #[test]
+    fn test_drop_pop() {
+        let (mut lvec, dropped) = drop_counters::<6>(3);
+        let popped = lvec.pop().unwrap();
+        assert_eq!(popped.id, 2);
+        assert!(dropped.borrow().is_empty());
+        drop(popped);
+        assert_eq!(*dropped.borrow(), vec![2]);
+        assert_all_dropped_once(lvec, &dropped, 3);
+    }
+
+    #[test]
+    fn test_drop_into_iter() {
+        let (lvec, dropped) = drop_counters::<6>(4);
+        let mut iter = lvec.into_iter();
+        let first = iter.next().unwrap();
+        assert_eq!(first.id, 0);
+        drop(iter);
+        assert_eq!(*dropped.borrow(), vec![1, 2, 3]);
+        drop(first);
+        assert_all_dropped_once(LimitedVec::<DropCounter, 6>::default(), &dropped, 4);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut lvec = (0..6).collect::<LimitedVec<u8, 8>>();
+        assert_eq!(lvec.drain(..2).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(lvec.drain(2..).rev().collect::<Vec<_>>(), vec![5, 4]);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(lvec.drain(..).count(), 2);
+        assert!(lvec.is_empty());
+    }
+
+    #[test]
+    fn test_swap_remove_split_off() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        assert_eq!(lvec.swap_remove(1), 2);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![1, 5, 3, 4]);
+
+        let tail = lvec.split_off(2);
+        assert_eq!(tail.into_vec(), vec![3, 4]);
+        assert_eq!(lvec.into_vec(), vec![1, 5]);
+    }
+
+    #[test]
+    fn test_occupied() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3]);
+        assert_eq!(lvec.occupied(), vec![&1, &2, &3]);
+        assert!(LimitedVec::<u8, 8>::new().occupied().is_empty());
+    }
+
+    #[test]
+    fn test_retain_range() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        lvec.retain_range(1..4, |i| i % 2 == 0);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4, 5]);
+        assert_eq!(lvec.free(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_retain_range_out_of_bounds() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3]);
+        lvec.retain_range(1..4, |i| i % 2 == 0);
+    }
+
+    #[test]
+    fn test_indices() {
+        let lvec = LimitedVec::<char, 8>::from(vec!['a', 'b', 'c']);
+        assert_eq!(lvec.indices().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let left = LimitedVec::<u8, 3>::from(vec![1, 3, 5]);
+        let right = LimitedVec::<u8, 4>::from(vec![2, 4, 6]);
+        let merged: LimitedVec<u8, 8> = left.merge_sorted(right);
+        assert_eq!(merged.into_vec(), vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_sorted_overflow() {
+        let left = LimitedVec::<u8, 3>::from(vec![1, 3, 5]);
+        let right = LimitedVec::<u8, 4>::from(vec![2, 4, 6]);
+        let _: LimitedVec<u8, 5> = left.merge_sorted(right);
+    }
+
+    #[test]
+    fn test_take_skip_while() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 0, 4]);
+        assert_eq!(
+            lvec.clone().take_while(|i| *i != 0).into_vec(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(lvec.skip_while(|i| *i != 0).into_vec(), vec![0, 4]);
+    }
+
+    #[test]
+    fn test_limvec_macro() {
+        let empty: LimitedVec<u8, 8> = crate::limvec![cap = 8;];
+        assert!(empty.is_empty());
+        assert_eq!(empty.capacity(), 8);
+
+        let lvec = crate::limvec![cap = 8; 1, 2, 3];
+        assert_eq!(lvec.capacity(), 8);
+        assert_eq!(lvec.into_vec(), vec![1, 2, 3]);
+
+        let full = crate::limvec![cap = 2; "a", "b",];
+        assert!(full.is_full());
+    }
+
+    #[test]
+    fn test_into_option_array() {
+        let lvec = LimitedVec::<u8, 4>::from(vec![1, 2]);
+        assert_eq!(lvec.into_option_array(), [Some(1), Some(2), None, None]);
+    }
+
+    #[test]
+    fn test_from_option_array() {
+        let lvec = LimitedVec::from_option_array([Some(1), Some(2), None, None]);
+        assert_eq!(lvec.into_vec(), vec![1, 2]);
+
+        let holey = [Some(1), None, Some(3), None];
+        assert_eq!(LimitedVec::try_from_option_array(holey), Err(holey));
+    }
+
+    #[test]
+    #[should_panic(expected = "Occupied slots must not follow a free slot")]
+    fn test_from_option_array_holey() {
+        let _ = LimitedVec::from_option_array([None, Some(2)]);
+    }
+
+    #[test]
+    fn test_rank_select() {
+        let lvec = LimitedVec::<u32, 8>::from(vec![10, 20, 30, 40]);
+        assert_eq!(lvec.rank(&5), 0);
+        assert_eq!(lvec.rank(&20), 1);
+        assert_eq!(lvec.rank(&25), 2);
+        assert_eq!(lvec.rank(&50), 4);
+
+        assert_eq!(lvec.select(0), Some(&10));
+        assert_eq!(lvec.select(3), Some(&40));
+        assert_eq!(lvec.select(4), None);
+    }
+
+    #[test]
+    fn test_collect_refs() {
+        let src = [1, 2, 3];
+        let refs = src.iter().collect::<LimitedVec<&i32, 8>>();
+        assert_eq!(refs.len(), 3);
+        assert!(refs
+            .iter()
+            .zip(src.iter())
+            .all(|(r, s)| std::ptr::eq(*r, s)));
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_serde_transparent() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+        #[serde(transparent)]
+        struct Wrapper(LimitedVec<u8, 4>);
+
+        let wrapper = Wrapper(LimitedVec::from(vec![1, 2, 3]));
+        let encoded = bincode::serialize(&wrapper).unwrap();
+        assert_eq!(encoded, bincode::serialize(&wrapper.0).unwrap());
+
+        let decoded: Wrapper = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, wrapper);
+    }
+
+    #[test]
+    fn test_resize_default() {
+        let mut lvec = LimitedVec::<u8, 4>::from(vec![1, 2]);
+        lvec.resize_default(4);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![1, 2, 0, 0]);
+        lvec.resize_default(1);
+        assert_eq!(lvec.into_vec(), vec![1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_resize_default_overflow() {
+        LimitedVec::<u8, 4>::new().resize_default(5);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut lvec = LimitedVec::<u8, 4>::from(vec![1, 3]);
+        lvec.insert(1, 2);
+        lvec.insert(3, 4);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_full() {
+        let mut lvec = LimitedVec::<u8, 2>::from(vec![1, 2]);
+        lvec.insert(0, 0);
+    }
+
+    #[test]
+    fn test_insert_sorted_by_key() {
+        use super::DupPolicy;
+
+        let mut lvec = LimitedVec::<(u8, char), 8>::from(vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+        let key = |i: &(u8, char)| i.0;
+
+        assert_eq!(
+            lvec.insert_sorted_by_key((2, 'x'), key, DupPolicy::AllowBefore),
+            Ok(1)
+        );
+        assert_eq!(
+            lvec.insert_sorted_by_key((2, 'y'), key, DupPolicy::AllowAfter),
+            Ok(3)
+        );
+        assert_eq!(
+            lvec.insert_sorted_by_key((2, 'z'), key, DupPolicy::Reject),
+            Err((2, 'z'))
+        );
+        assert_eq!(
+            lvec.insert_sorted_by_key((0, 'z'), key, DupPolicy::Reject),
+            Ok(0)
+        );
+        assert_eq!(
+            lvec.into_vec(),
+            vec![(0, 'z'), (1, 'a'), (2, 'x'), (2, 'b'), (2, 'y'), (3, 'c')]
+        );
+    }
+
+    #[test]
+    fn test_drain_while() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4]);
+        assert_eq!(lvec.drain_while(|i| *i < 3).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(lvec.drain_while(|i| *i > 3).count(), 0);
+        assert_eq!(lvec.len(), 2);
+    }
+
+    #[test]
+    fn test_hash() {
+        let lvec = LimitedVec::<u8, 4>::from(vec![1, 2, 3]);
+
+        // Same items reached through a different history of operations
+        let mut other = LimitedVec::<u8, 4>::from(vec![0, 1, 2, 3]);
+        let _ = other.remove(0);
+        assert_eq!(lvec, other);
+        assert_eq!(hash_of(&lvec), hash_of(&other));
+
+        other.push(4);
+        assert_ne!(hash_of(&lvec), hash_of(&other));
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_bytes_json() {
+        use super::bytes::LimitedBytes;
+
+        let bytes = LimitedBytes(LimitedVec::<u8, 8>::from(vec![0x00, 0xab, 0x10]));
+        let json = serde_json::to_string(&bytes).unwrap();
+        assert_eq!(json, "\"00ab10\"");
+        assert_eq!(
+            serde_json::from_str::<LimitedBytes<8>>(&json).unwrap(),
+            bytes
+        );
+
+        assert!(serde_json::from_str::<LimitedBytes<2>>(&json).is_err());
+        assert!(serde_json::from_str::<LimitedBytes<8>>("\"0g\"").is_err());
+        assert!(serde_json::from_str::<LimitedBytes<8>>("\"+a\"").is_err());
+        assert!(serde_json::from_str::<LimitedBytes<4>>("\"+a+b\"").is_err());
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_bytes_bincode() {
+        use super::bytes::LimitedBytes;
+
+        let bytes = LimitedBytes(LimitedVec::<u8, 8>::from(vec![0x00, 0xab, 0x10]));
+        let encoded = bincode::serialize(&bytes).unwrap();
+        assert_eq!(
+            encoded,
+            bincode::serialize(&vec![0x00u8, 0xab, 0x10]).unwrap()
+        );
+        assert_eq!(
+            bincode::deserialize::<LimitedBytes<8>>(&encoded).unwrap(),
+            bytes
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4]);
+        let evens = lvec.filter(|i| i % 2 == 0);
+        assert_eq!(evens.iter().copied().collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(evens.free(), 6);
+    }
+
+    #[test]
+    fn test_assert_free_at_least() {
+        let lvec = LimitedVec::<u8, 4>::from(vec![1, 2]);
+        lvec.assert_free_at_least(0);
+        lvec.assert_free_at_least(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "LimitedVec needs 3 free slots but only has 2")]
+    fn test_assert_free_at_least_panics() {
+        LimitedVec::<u8, 4>::from(vec![1, 2]).assert_free_at_least(3);
+    }
+
+    #[test]
+    fn test_iter_progress() {
+        let lvec = LimitedVec::<char, 8>::from(vec!['a', 'b', 'c']);
+        assert_eq!(
+            lvec.iter_progress().collect::<Vec<_>>(),
+            vec![(0, 3, &'a'), (1, 3, &'b'), (2, 3, &'c')]
+        );
+    }
+
+    #[test]
+    fn test_trim() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3]);
+        let trimmed = lvec.trim();
+        assert_eq!(trimmed, vec![1, 2, 3]);
+        assert_eq!(trimmed.len(), 3);
+    }
+
+    #[test]
+    fn test_try_retain() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        let mut calls = 0;
+        let result = lvec.try_retain(|i| {
+            calls += 1;
+            if *i == 3 {
+                return Err("three");
+            }
+            Ok(i % 2 == 0)
+        });
+        assert_eq!(result, Err("three"));
+        assert_eq!(calls, 3);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+
+        assert_eq!(lvec.try_retain(|i| Ok::<_, ()>(*i > 3)), Ok(()));
+        assert_eq!(lvec.into_vec(), vec![4, 5]);
+    }
+
+    #[test]
+    fn test_positions() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4]);
+        assert_eq!(
+            lvec.positions(|i| i % 2 == 0).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(lvec.positions(|i| *i > 4).count(), 0);
+    }
+
+    #[test]
+    fn test_capacity_error() {
+        use super::error::CapacityError;
+
+        let mut lvec = LimitedVec::<String, 2>::default();
+        assert!(lvec.try_push("a".to_string()).is_ok());
+        assert!(lvec.try_insert(0, "b".to_string()).is_ok());
+
+        match lvec.try_push("c".to_string()) {
+            Err(err) => assert_eq!(err.into_inner(), "c"),
+            Ok(()) => panic!("Pushing onto a full LimitedVec must fail"),
+        }
+        let err: CapacityError<String> = lvec.try_insert(1, "d".to_string()).unwrap_err();
+        assert_eq!(err.to_string(), "LimitedVec is full");
+        assert_eq!(err.into_inner(), "d");
+        assert_eq!(lvec.into_vec(), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_truncate_drop_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let (lvec, dropped) = drop_counters::<6>(5);
+        let mut lvec = lvec
+            .into_iter()
+            .map(PanicOnDrop)
+            .collect::<LimitedVec<_, 6>>();
+        let result = catch_unwind(AssertUnwindSafe(|| lvec.truncate(1)));
+        assert!(result.is_err());
+        assert_eq!(lvec.len(), 1);
+        assert_eq!(lvec.free(), 5);
+        assert_eq!(*dropped.borrow(), vec![1, 2, 3, 4]);
+
+        drop(lvec);
+        assert_eq!(*dropped.borrow(), vec![1, 2, 3, 4, 0]);
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_serde_default() {
+        #[derive(serde::Deserialize)]
+        struct Config {
+            name: String,
+            #[serde(default)]
+            values: LimitedVec<u8, 4>,
+        }
+
+        let config: Config = serde_json::from_str(r#"{"name": "empty"}"#).unwrap();
+        assert_eq!(config.name, "empty");
+        assert!(config.values.is_empty());
+
+        let config: Config = serde_json::from_str(r#"{"name": "set", "values": [1, 2]}"#).unwrap();
+        assert_eq!(config.values.into_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_get_back() {
+        let lvec = LimitedVec::<u8, 4>::from(vec![10, 20, 30]);
+        assert_eq!(lvec.get_back(0), Some(&30));
+        assert_eq!(lvec.get_back(2), Some(&10));
+        assert_eq!(lvec.get_back(3), None);
+        assert_eq!(LimitedVec::<u8, 4>::new().get_back(0), None);
+    }
+
+    #[test]
+    fn test_eq_different_capacities() {
+        let small = LimitedVec::<u8, 4>::from(vec![1, 2, 3]);
+        let large = LimitedVec::<u8, 8>::from(vec![1, 2, 3]);
+        assert_eq!(small, large);
+        assert_eq!(large, small);
+
+        assert_eq!(hash_of(&small), hash_of(&large));
+
+        assert_ne!(small, LimitedVec::<u8, 8>::from(vec![1, 2]));
+        assert_ne!(small, LimitedVec::<u8, 8>::from(vec![1, 2, 4]));
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let mut lvec = LimitedVec::<String, 4>::from(vec!["a".to_string(), "b".to_string()]);
+        let mut cloned = lvec.to_vec();
+        cloned.push("c".to_string());
+        lvec.last_mut().unwrap().push('!');
+        assert_eq!(cloned, vec!["a", "b", "c"]);
+        assert_eq!(lvec.into_vec(), vec!["a", "b!"]);
+    }
+
+    #[test]
+    fn test_rchunks_exact() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        let mut chunks = lvec.rchunks_exact(2);
+        assert_eq!(chunks.next().unwrap().collect::<Vec<_>>(), vec![&4, &5]);
+        assert_eq!(chunks.next().unwrap().collect::<Vec<_>>(), vec![&2, &3]);
+        assert!(chunks.next().is_none());
+        assert_eq!(chunks.remainder().collect::<Vec<_>>(), vec![&1]);
+
+        assert_eq!(lvec.rchunks_exact(5).count(), 1);
+        assert_eq!(lvec.rchunks_exact(6).remainder().count(), 5);
+    }
+
+    #[test]
+    fn test_try_first_last() {
+        use super::error::EmptyError;
+
+        let mut lvec = LimitedVec::<u8, 4>::new();
+        assert_eq!(lvec.try_first(), Err(EmptyError));
+        assert_eq!(lvec.try_last(), Err(EmptyError));
+        assert_eq!(lvec.first_mut(), None);
+
+        lvec.extend([1, 2, 3]);
+        *lvec.first_mut().unwrap() = 0;
+        assert_eq!(lvec.try_first(), Ok(&0));
+        assert_eq!(lvec.try_last(), Ok(&3));
+    }
+
+    #[test]
+    fn test_swap_remove_last() {
+        let mut lvec = LimitedVec::<u8, 4>::from(vec![1, 2, 3]);
+        assert_eq!(lvec.swap_remove(2), 3);
+        assert_eq!(lvec.len(), 2);
+        assert_eq!(lvec.free(), 2);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        assert_eq!(lvec.swap_remove(1), 2);
+        assert_eq!(lvec.swap_remove(0), 1);
+        assert!(lvec.is_empty());
+        assert_eq!(lvec.free(), 4);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Can't collect more elements into LimitedVec than capacity (4): element at index 4 doesn't fit"
+    )]
+    fn test_from_iter_overflow_message() {
+        let _ = (0..10).map(|i| i * 2).collect::<LimitedVec<u8, 4>>();
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_deserialize_bounded() {
+        let mut de = serde_json::Deserializer::from_str("[1, 2]");
+        let lvec = LimitedVec::<u8, 8>::deserialize_bounded(&mut de, 2).unwrap();
+        assert_eq!(lvec.into_vec(), vec![1, 2]);
+
+        let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+        assert!(LimitedVec::<u8, 8>::deserialize_bounded(&mut de, 2).is_err());
+
+        // The capacity bounds the default deserialization
+        assert!(serde_json::from_str::<LimitedVec<u8, 2>>("[1, 2, 3]").is_err());
+    }
+
+    #[test]
+    fn test_as_mut_array_compact() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 3, 4]);
+        let slots = lvec.as_mut_array();
+        slots[1] = None;
+        slots[5] = Some(6);
+        lvec.compact();
+        assert_eq!(
+            lvec.as_array(),
+            &[Some(1), Some(3), Some(4), Some(6), None, None]
+        );
+        assert_eq!(lvec.len(), 4);
+        assert_eq!(lvec.free(), 2);
+        assert_eq!(lvec.into_vec(), vec![1, 3, 4, 6]);
+    }
+
+    #[test]
+    fn test_iter_mut_holey() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 3, 4]);
+        lvec.as_mut_array()[1] = None;
+        for i in lvec.iter_mut() {
+            *i *= 10;
+        }
+        assert_eq!(lvec.iter_mut().count(), 3);
+        lvec.compact();
+        assert_eq!(lvec.into_vec(), vec![10, 30, 40]);
+    }
+
+    #[test]
+    fn test_invariant_packed() {
+        let states: [LimitedVec<u8, 4>; 3] = [
+            LimitedVec::new(),
+            LimitedVec::from(vec![1, 2]),
+            LimitedVec::from(vec![1, 2, 3, 4]),
+        ];
+        for (lvec, len) in states.iter().zip([0, 2, 4]) {
+            assert_eq!(lvec.len(), len);
+            assert_eq!(lvec.count_occupied(), len);
+            assert_eq!(lvec.is_empty(), len == 0);
+            assert_eq!(lvec.is_full(), len == 4);
+            assert_eq!(lvec.free(), 4 - len);
+            assert_eq!(lvec.last_idx(), len.checked_sub(1));
+            assert_eq!(lvec.get(len), None);
+            assert_eq!(lvec.iter().count(), len);
+            assert_eq!(lvec.indices().count(), len);
+            if let Some(last) = len.checked_sub(1) {
+                assert_eq!(lvec.get(last), Some(&(last as u8 + 1)));
+            }
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_invariant_holey() {
+        use std::panic::catch_unwind;
+
+        let mut holey = LimitedVec::<u8, 4>::from(vec![1, 2, 3]);
+        holey.as_mut_array()[1] = None;
+        assert_eq!(holey.count_occupied(), 2);
+        assert_eq!(holey.indices().collect::<Vec<_>>(), vec![0, 2]);
+
+        let checks: [fn(&LimitedVec<u8, 4>); 6] = [
+            |lvec| {
+                let _ = lvec.len();
+            },
+            |lvec| {
+                let _ = lvec.is_empty();
+            },
+            |lvec| {
+                let _ = lvec.free();
+            },
+            |lvec| {
+                let _ = lvec.last_idx();
+            },
+            |lvec| {
+                let _ = lvec.get(0);
+            },
+            |lvec| {
+                let _ = lvec.iter();
+            },
+        ];
+        for check in checks {
+            assert!(catch_unwind(|| check(&holey)).is_err());
+        }
+
+        holey.compact();
+        assert_eq!(holey.len(), 2);
+        assert_eq!(holey.free(), 2);
+    }
+
+    #[test]
+    fn test_filled_default() {
+        let lvec = LimitedVec::<i32, 4>::filled_default();
+        assert_eq!(lvec.len(), 4);
+        assert!(lvec.is_full());
+        assert!(lvec.iter().all(|i| *i == 0));
+    }
+
+    #[test]
+    fn test_try_into_capacity() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3]);
+        let small: LimitedVec<u8, 3> = lvec.clone().try_into_capacity().unwrap();
+        assert_eq!(small, lvec);
+        assert!(small.is_full());
+
+        let err = lvec.clone().try_into_capacity::<2>().unwrap_err();
+        assert_eq!(err, lvec);
+    }
+
+    #[test]
+    fn test_peek_buffer() {
+        let lvec = LimitedVec::<char, 8>::from(vec!['a', 'b', 'c', 'd']);
+        let mut tokens = lvec.peekable();
+        assert_eq!(tokens.peek_ahead(0), Some(&'a'));
+        assert_eq!(tokens.peek_ahead(2), Some(&'c'));
+
+        assert_eq!(tokens.next(), Some(&'a'));
+        assert_eq!(tokens.peek_ahead(0), Some(&'b'));
+        assert_eq!(tokens.peek_ahead(2), Some(&'d'));
+        assert_eq!(tokens.peek_ahead(3), None);
+        assert_eq!(tokens.peek_ahead(usize::MAX), None);
+        assert_eq!(tokens.collect::<Vec<_>>(), vec![&'b', &'c', &'d']);
+    }
+
+    #[test]
+    fn test_retain_changed() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4]);
+        assert!(!lvec.retain_changed(|i| *i < 10));
+        assert_eq!(lvec.len(), 4);
+        assert!(lvec.retain_changed(|i| *i != 3));
+        assert_eq!(lvec.into_vec(), vec![1, 2, 4]);
+    }
 
-        assert_eq!(vec.pop(), Some(42));
-        assert_eq!(vec.len(), 0);
-        assert_eq!(vec.free(), SIZE);
+    #[test]
+    fn test_split_into() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5, 6]);
+        let [a, b, c] = lvec.split_into::<3, 2>();
+        assert_eq!(a.into_vec(), vec![1, 2]);
+        assert_eq!(b.into_vec(), vec![3, 4]);
+        assert_eq!(c.into_vec(), vec![5, 6]);
+
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        let parts = lvec.split_into::<3, 2>().map(LimitedVec::into_vec);
+        assert_eq!(parts, [vec![1, 2], vec![3, 4], vec![5]]);
     }
 
     #[test]
-    fn test_from_vec() {
-        const SIZE: usize = 10;
-        let src_vec = (0..7).collect::<Vec<usize>>();
-        let lvec = LimitedVec::<_, SIZE>::from(src_vec.clone());
+    #[should_panic]
+    fn test_split_into_overflow() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5, 6, 7]);
+        let _ = lvec.split_into::<3, 2>();
+    }
+
+    #[test]
+    fn test_interleave() {
+        let left = LimitedVec::<u8, 4>::from(vec![1, 3, 5]);
+        let right = LimitedVec::<u8, 2>::from(vec![2, 4]);
+        let interleaved: LimitedVec<u8, 6> = left.interleave(right);
+        assert_eq!(interleaved.into_vec(), vec![1, 2, 3, 4, 5]);
+
+        let left = LimitedVec::<u8, 4>::from(vec![1]);
+        let right = LimitedVec::<u8, 4>::from(vec![2, 4, 6]);
+        let interleaved: LimitedVec<u8, 4> = left.interleave(right);
+        assert_eq!(interleaved.into_vec(), vec![1, 2, 4, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_interleave_overflow() {
+        let left = LimitedVec::<u8, 4>::from(vec![1, 3, 5]);
+        let right = LimitedVec::<u8, 2>::from(vec![2, 4]);
+        let _: LimitedVec<u8, 4> = left.interleave(right);
+    }
+
+    #[test]
+    fn test_unzip() {
+        let lvec = LimitedVec::<(u8, char), 4>::from(vec![(1, 'a'), (2, 'b')]);
+        let (numbers, chars) = lvec.unzip();
+        assert_eq!(numbers.into_vec(), vec![1, 2]);
+        assert_eq!(chars.into_vec(), vec!['a', 'b']);
+    }
+
+    #[test]
+    fn test_retain_drop_order() {
+        let (mut lvec, dropped) = drop_counters::<8>(6);
+        lvec.retain(|i| ![4, 1, 3].contains(&i.id));
+        assert_eq!(*dropped.borrow(), vec![1, 3, 4]);
+        assert_eq!(ids(&lvec), vec![0, 2, 5]);
+
+        let lvec = lvec.filter(|i| i.id == 2);
+        assert_eq!(*dropped.borrow(), vec![1, 3, 4, 0, 5]);
+        assert_all_dropped_once(lvec, &dropped, 6);
+    }
+
+    #[test]
+    fn test_push_family() {
+        use std::panic::catch_unwind;
+
+        let mut lvec = LimitedVec::<u32, 2>::new();
+        lvec.push(1);
+        assert!(lvec.try_push(2).is_ok());
+        assert_eq!(lvec.try_push(3).unwrap_err().into_inner(), 3);
+        assert!(!lvec.saturating_push(4));
+        let mut full = lvec.clone();
+        assert!(catch_unwind(move || full.push(5)).is_err());
+
+        let mut lvec = LimitedVec::<u32, 2>::new();
+        assert!(lvec.saturating_push(1));
+        assert!(lvec.saturating_insert(0, 2));
+        assert!(!lvec.saturating_insert(1, 3));
+        assert_eq!(lvec.try_insert(2, 4).unwrap_err().into_inner(), 4);
+        let mut full = lvec.clone();
+        assert!(catch_unwind(move || full.insert(0, 5)).is_err());
+        assert_eq!(lvec.into_vec(), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_into_iter_len() {
+        let lvec = limvec![cap = 5; 1u32, 2, 3];
+        let mut iter = lvec.into_iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+
+        let lvec = limvec![cap = 5; 4u32, 5];
+        assert_eq!(lvec.into_iter().collect::<Vec<_>>(), vec![4, 5]);
+    }
+
+    #[test]
+    fn test_from_slots_holes() {
+        let slots = [None, Some(1), None, Some(2), Some(3), None];
+        let mut lvec = LimitedVec::<u8, 6>::from_slots(slots);
+        assert!(!lvec.is_packed());
+        assert_eq!(lvec.count_occupied(), 3);
+        assert_eq!(lvec.indices().collect::<Vec<_>>(), vec![1, 3, 4]);
+
+        for i in lvec.iter_mut() {
+            *i += 10;
+        }
         assert_eq!(
-            lvec.iter().collect::<Vec<_>>(),
-            src_vec.iter().collect::<Vec<_>>()
+            lvec.as_array(),
+            &[None, Some(11), None, Some(12), Some(13), None]
         );
+
+        lvec.compact();
+        assert!(lvec.is_packed());
+        assert_eq!(lvec.len(), 3);
+        assert_eq!(lvec.indices().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(lvec.into_vec(), vec![11, 12, 13]);
+
+        let mut tail = LimitedVec::<u8, 3>::from_slots([None, None, Some(1)]);
+        assert_eq!(tail.iter_mut().count(), 1);
+        tail.compact();
+        assert_eq!(tail.as_array(), &[Some(1), None, None]);
     }
 
     #[test]
-    fn test_from_iter() {
-        const SIZE: usize = 10;
-        let src_vec = (0..7).collect::<Vec<usize>>();
-        let lvec = (0..7).collect::<LimitedVec<usize, SIZE>>();
+    fn test_repeat_fill() {
+        let mut lvec = LimitedVec::<u8, 5>::from(vec![1, 2]);
+        lvec.repeat_fill(0, 3);
+        assert_eq!(lvec.clone().into_vec(), vec![1, 2, 0, 0, 0]);
+        lvec.repeat_fill(9, 0);
+        assert!(lvec.is_full());
+
+        let mut lvec = LimitedVec::<u8, 3>::from(vec![1]);
+        let mut padded = lvec.clone();
+        assert!(std::panic::catch_unwind(move || padded.repeat_fill(0, 3)).is_err());
+        lvec.repeat_fill(7, 2);
+        assert_eq!(lvec.into_vec(), vec![1, 7, 7]);
+    }
+
+    #[test]
+    fn test_as_slice_cow() {
+        let lvec = LimitedVec::<u8, 5>::from(vec![1, 2, 3]);
+        let slice = lvec.as_slice_cow();
+        assert_eq!(&*slice, &[1, 2, 3]);
+        assert_eq!(slice.len(), lvec.len());
+        assert!(LimitedVec::<u8, 2>::new().as_slice_cow().is_empty());
+    }
+
+    #[test]
+    fn test_occupancy_mask() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3]);
+        assert_eq!(lvec.occupancy_mask(), 0b111);
+        assert_eq!(lvec.occupancy_mask_u128(), 0b111);
+        assert_eq!(LimitedVec::<u8, 8>::new().occupancy_mask(), 0);
         assert_eq!(
-            lvec.iter().collect::<Vec<_>>(),
-            src_vec.iter().collect::<Vec<_>>()
+            LimitedVec::<u8, 64>::filled_default().occupancy_mask(),
+            u64::MAX
+        );
+        assert_eq!(
+            LimitedVec::<u8, 128>::filled_default().occupancy_mask_u128(),
+            u128::MAX
         );
+
+        let holey = LimitedVec::<u8, 4>::from_slots([Some(1), None, Some(3), None]);
+        assert_eq!(holey.occupancy_mask(), 0b101);
     }
 
     #[test]
-    fn test_extend() {
-        const SIZE: usize = 50;
-        let src_vec = (0..7).collect::<Vec<usize>>();
-        let ext_data = (7..25).collect::<Vec<_>>();
+    fn test_rotate_to_front() {
+        let mut lvec = LimitedVec::<char, 5>::from(vec!['a', 'b', 'c', 'd']);
+        lvec.rotate_to_front(2);
+        assert_eq!(lvec.clone().into_vec(), vec!['c', 'a', 'b', 'd']);
+        lvec.rotate_to_front(0);
+        lvec.rotate_to_front(3);
+        assert_eq!(lvec.clone().into_vec(), vec!['d', 'c', 'a', 'b']);
+        assert!(std::panic::catch_unwind(move || lvec.rotate_to_front(4)).is_err());
+    }
 
-        let mut lvec = LimitedVec::<_, SIZE>::from_iter(src_vec.iter().copied());
-        lvec.extend(ext_data.iter().copied());
-        let expected = src_vec
+    #[test]
+    fn test_truncate_front() {
+        let (mut lvec, dropped) = drop_counters::<6>(5);
+        lvec.truncate_front(2);
+        assert_eq!(ids(&lvec), vec![3, 4]);
+        assert_eq!(*dropped.borrow(), vec![0, 1, 2]);
+        lvec.truncate_front(3);
+        assert_eq!(lvec.free(), 4);
+        assert_all_dropped_once(lvec, &dropped, 5);
+
+        let mut lvec = LimitedVec::<u8, 5>::from(vec![1, 2, 3, 4, 5]);
+        lvec.truncate_front(2);
+        assert_eq!(lvec.clone().into_vec(), vec![4, 5]);
+        lvec.truncate_front(0);
+        assert!(lvec.is_empty());
+    }
+
+    #[test]
+    fn test_equal_range() {
+        let lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 2, 2, 3]);
+        assert_eq!(lvec.equal_range(&2), 1..4);
+        assert_eq!(lvec.equal_range(&1), 0..1);
+        assert_eq!(lvec.equal_range(&0), 0..0);
+        assert_eq!(lvec.equal_range(&4), 5..5);
+        assert_eq!(LimitedVec::<u8, 2>::new().equal_range(&1), 0..0);
+    }
+
+    #[test]
+    fn test_iter_adapters() {
+        let vec = vec![1u8, 2, 3, 4, 5];
+        let lvec = LimitedVec::<u8, 8>::from(vec.clone());
+
+        assert_eq!(lvec.iter().len(), vec.iter().len());
+        assert!(lvec
             .iter()
-            .copied()
-            .chain(ext_data.iter().copied())
+            .rev()
+            .enumerate()
+            .eq(vec.iter().rev().enumerate()));
+        assert!(lvec.iter().rev().take(2).eq(vec.iter().rev().take(2)));
+        assert!(lvec.iter().skip(1).rev().eq(vec.iter().skip(1).rev()));
+        assert!(lvec
+            .iter()
+            .rev()
+            .skip(1)
+            .step_by(2)
+            .eq(vec.iter().rev().skip(1).step_by(2)));
+        assert_eq!(lvec.iter().rev().take(2).len(), 2);
+        assert_eq!(lvec.iter().enumerate().next_back(), Some((4, &5)));
+        assert_eq!(lvec.iter().zip(lvec.iter().rev()).len(), vec.len());
+
+        let mut iter = lvec.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.len(), 3);
+        assert!(iter.by_ref().rev().eq([&4, &3, &2]));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_pop_front_n() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 3, 4]);
+        let popped = lvec.pop_front_n(2);
+        assert_eq!(popped.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(lvec.free(), 4);
+
+        let popped = lvec.pop_front_n(5);
+        assert_eq!(popped.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert!(lvec.is_empty());
+        assert!(lvec.pop_front_n(1).is_empty());
+    }
+
+    #[test]
+    fn test_retain_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let (mut lvec, dropped) = drop_counters::<6>(5);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            lvec.retain(|i| {
+                assert_ne!(i.id, 2, "Predicate panics on item 2");
+                i.id != 1
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(ids(&lvec), vec![0, 2, 3, 4]);
+        assert_eq!(lvec.free(), 2);
+        assert_eq!(*dropped.borrow(), vec![1]);
+        assert_all_dropped_once(lvec, &dropped, 5);
+
+        let (mut lvec, dropped) = drop_counters::<6>(5);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            lvec.retain_split(|i| {
+                assert_ne!(i.id, 3, "Predicate panics on item 3");
+                i.id % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(ids(&lvec), vec![0, 2, 3, 4]);
+        assert_eq!(*dropped.borrow(), vec![1]);
+        assert_all_dropped_once(lvec, &dropped, 5);
+    }
+
+    #[test]
+    fn test_try_map() {
+        let lvec = LimitedVec::<&str, 4>::from(vec!["1", "2", "3"]);
+        let parsed = lvec.try_map(|i| i.parse::<u8>()).unwrap();
+        assert_eq!(parsed.into_vec(), vec![1, 2, 3]);
+
+        let lvec = LimitedVec::<&str, 4>::from(vec!["1", "x", "3"]);
+        assert!(lvec.try_map(|i| i.parse::<u8>()).is_err());
+
+        let (lvec, dropped) = drop_counters::<6>(5);
+        let mapped_dropped = dropped.clone();
+        let result = lvec.try_map(|i| {
+            if i.id == 2 {
+                return Err(i.id);
+            }
+            Ok(DropCounter {
+                id: i.id + 10,
+                dropped: mapped_dropped.clone(),
+            })
+        });
+        assert_eq!(result.unwrap_err(), 2);
+        let mut all = dropped.borrow().clone();
+        all.sort_unstable();
+        assert_eq!(all, vec![0, 1, 2, 3, 4, 10, 11]);
+    }
+
+    #[test]
+    fn test_sort_by_cached_key() {
+        let mut lvec = LimitedVec::<&str, 6>::from(vec!["ccc", "a", "dddd", "bb", "e"]);
+        let mut calls = 0;
+        lvec.sort_by_cached_key(|i| {
+            calls += 1;
+            i.len()
+        });
+        assert_eq!(calls, 5);
+        assert_eq!(lvec.into_vec(), vec!["a", "e", "bb", "ccc", "dddd"]);
+    }
+
+    #[test]
+    fn test_contains_sorted() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 3, 3, 5, 8]);
+        for x in [1, 3, 5, 8] {
+            assert!(lvec.contains_sorted(&x));
+        }
+        for x in [0, 2, 4, 9] {
+            assert!(!lvec.contains_sorted(&x));
+        }
+        assert!(!LimitedVec::<u8, 2>::new().contains_sorted(&0));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "contains_sorted requires the items to be sorted")]
+    fn test_contains_sorted_unsorted() {
+        let lvec = LimitedVec::<u8, 4>::from(vec![3, 1, 2]);
+        let _ = lvec.contains_sorted(&1);
+    }
+
+    #[test]
+    fn test_split_chunk() {
+        let lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 3, 4]);
+        let (header, rest) = lvec.split_first_chunk::<2>().unwrap();
+        assert_eq!(header, [&1, &2]);
+        assert_eq!(rest.copied().collect::<Vec<_>>(), vec![3, 4]);
+
+        let (rest, footer) = lvec.split_last_chunk::<3>().unwrap();
+        assert_eq!(rest.copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(footer, [&2, &3, &4]);
+
+        let (header, rest) = lvec.split_first_chunk::<4>().unwrap();
+        assert_eq!(header, [&1, &2, &3, &4]);
+        assert_eq!(rest.len(), 0);
+        assert!(lvec.split_first_chunk::<5>().is_none());
+        assert!(lvec.split_last_chunk::<5>().is_none());
+    }
+
+    #[test]
+    fn test_concat() {
+        let lvec = LimitedVec::<&str, 4>::from(vec!["foo", "bar"]);
+        assert_eq!(lvec.concat(), "foobar");
+        let owned = LimitedVec::<String, 4>::from(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(owned.concat(), "ab");
+        assert_eq!(LimitedVec::<&str, 2>::new().concat(), "");
+    }
+
+    #[test]
+    fn test_index_of() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![4, 7, 9, 7]);
+        assert_eq!(lvec.index_of(&7), Some(1));
+        assert_eq!(lvec.index_of(&4), Some(0));
+        assert_eq!(lvec.index_of(&5), None);
+        let idx = lvec.index_of(&9).unwrap();
+        assert_eq!(lvec.remove(idx), 9);
+        assert_eq!(lvec.index_of(&9), None);
+    }
+
+    #[test]
+    fn test_drain_filter_into() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut lvec = LimitedVec::<u8, 4>::from(vec![1, 2, 3, 4]);
+        let mut dest = LimitedVec::<u8, 4>::new();
+        lvec.drain_filter_into(&mut dest, |i| i % 2 == 1);
+        assert_eq!(lvec.clone().into_vec(), vec![2, 4]);
+        assert_eq!(dest.clone().into_vec(), vec![1, 3]);
+
+        let mut lvec = LimitedVec::<u8, 4>::from(vec![5, 6, 7, 8]);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            lvec.drain_filter_into(&mut dest, |_| true)
+        }));
+        assert!(result.is_err());
+        assert_eq!(lvec.into_vec(), vec![7, 8]);
+        assert_eq!(dest.into_vec(), vec![1, 3, 5, 6]);
+    }
+
+    #[test]
+    fn test_bounded_capacity() {
+        use super::BoundedCapacity;
+
+        fn fill_ratio<C: BoundedCapacity>(container: &C) -> (usize, usize) {
+            (container.current_len(), C::CAPACITY)
+        }
+
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3]);
+        assert_eq!(fill_ratio(&lvec), (3, 8));
+        assert_eq!(fill_ratio(&LimitedVec::<String, 2>::default()), (0, 2));
+        assert_eq!(<LimitedVec<u8, 5> as BoundedCapacity>::CAPACITY, 5);
+    }
+
+    #[test]
+    fn test_retain_returning_len() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        assert_eq!(lvec.retain_returning_len(|i| i % 2 == 1), 3);
+        assert_eq!(lvec.retain_returning_len(|_| true), 3);
+        assert_eq!(lvec.retain_returning_len(|_| false), 0);
+        assert!(lvec.is_empty());
+    }
+
+    #[test]
+    fn test_from_iter_rev() {
+        let lvec = LimitedVec::<u8, 4>::from_iter_rev(0..3);
+        assert_eq!(lvec.into_vec(), vec![2, 1, 0]);
+        assert!(LimitedVec::<u8, 4>::from_iter_rev(None).is_empty());
+        assert!(std::panic::catch_unwind(|| LimitedVec::<u8, 2>::from_iter_rev(0..3)).is_err());
+    }
+
+    #[test]
+    fn test_retain_rev() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        let mut seen = Vec::new();
+        lvec.retain_rev(|i| {
+            seen.push(*i);
+            *i != 2 && *i != 5
+        });
+        assert_eq!(seen, vec![5, 4, 3, 2, 1]);
+        assert_eq!(lvec.into_vec(), vec![1, 3, 4]);
+
+        let (mut lvec, dropped) = drop_counters::<6>(5);
+        lvec.retain_rev(|i| i.id == 2);
+        assert_eq!(ids(&lvec), vec![2]);
+        assert_eq!(*dropped.borrow(), vec![4, 3, 1, 0]);
+        assert_all_dropped_once(lvec, &dropped, 5);
+    }
+
+    #[test]
+    fn test_into_chunks() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        let chunks = lvec
+            .into_chunks::<2>()
+            .map(LimitedVec::into_vec)
             .collect::<Vec<_>>();
-        assert_eq!(lvec.iter().copied().collect::<Vec<_>>(), expected);
+        assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(LimitedVec::<u8, 4>::new().into_chunks::<2>().count(), 0);
+
+        let (lvec, dropped) = drop_counters::<6>(5);
+        let mut chunks = lvec.into_chunks::<3>();
+        let first = chunks.next().unwrap();
+        assert_eq!(ids(&first), vec![0, 1, 2]);
+        drop(chunks);
+        assert_eq!(*dropped.borrow(), vec![3, 4]);
+        assert_all_dropped_once(first, &dropped, 5);
+    }
+
+    #[test]
+    fn test_retain_dedup() {
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 1, 2, 2, 3]);
+        lvec.retain_dedup(|i| *i != 2);
+        assert_eq!(lvec.into_vec(), vec![1, 3]);
+
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 1, 3, 3, 1]);
+        lvec.retain_dedup(|i| *i != 2);
+        assert_eq!(lvec.into_vec(), vec![1, 3, 1]);
+    }
+
+    #[test]
+    fn test_last_index_of() {
+        let lvec = LimitedVec::<u8, 6>::from(vec![1, 2, 1, 3]);
+        assert_eq!(lvec.last_index_of(&1), Some(2));
+        assert_eq!(lvec.last_index_of(&3), Some(3));
+        assert_eq!(lvec.last_index_of(&4), None);
     }
 
     #[cfg(feature = "with_serde")]
     #[test]
-    fn test_bincode() {
-        let iter = (0..10).map(|i| format!("Number: {i}"));
-        let lvec = LimitedVec::<String, 14>::from_iter(iter);
+    fn test_serde_option_items() {
+        let lvec = LimitedVec::<Option<u8>, 4>::from(vec![Some(1), None, Some(3)]);
 
         let encoded = bincode::serialize(&lvec).unwrap();
-        let decoded: LimitedVec<String, 14> = bincode::deserialize(&encoded).unwrap();
-
+        let decoded: LimitedVec<Option<u8>, 4> = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded.get(1), Some(&None));
         assert_eq!(lvec, decoded);
+
+        let json = serde_json::to_string(&lvec).unwrap();
+        assert_eq!(json, "[1,null,3]");
+        let decoded: LimitedVec<Option<u8>, 4> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.into_vec(), vec![Some(1), None, Some(3)]);
+    }
+
+    #[test]
+    fn test_windows_fold() {
+        let lvec = LimitedVec::<u32, 6>::from(vec![1, 2, 3, 4]);
+        let sums = lvec.windows_fold(Vec::new(), |mut sums, [a, b]| {
+            sums.push(*a + *b);
+            sums
+        });
+        assert_eq!(sums, vec![3, 5, 7]);
+        assert_eq!(lvec.windows_fold::<4, _, _>(0, |n, _| n + 1), 1);
+        assert_eq!(lvec.windows_fold::<5, _, _>(0, |n, _| n + 1), 0);
+        assert_eq!(
+            LimitedVec::<u32, 2>::new().windows_fold::<1, _, _>(0, |n, _| n + 1),
+            0
+        );
+    }
+
+    #[test]
+    fn test_swap_remove_many() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![10, 20, 30, 40]);
+        let removed = lvec.swap_remove_many(&[0, 2]);
+        assert_eq!(removed.into_vec(), vec![30, 10]);
+        assert_eq!(lvec.clone().into_vec(), vec![40, 20]);
+        assert!(lvec.swap_remove_many(&[]).is_empty());
+
+        let mut dup = lvec.clone();
+        assert!(std::panic::catch_unwind(move || dup.swap_remove_many(&[1, 1])).is_err());
+        assert!(std::panic::catch_unwind(move || lvec.swap_remove_many(&[2])).is_err());
+    }
+
+    #[test]
+    fn test_retain_controlled() {
+        use super::RetainAction;
+
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 5, 3, 1, 2]);
+        let mut calls = 0;
+        lvec.retain_controlled(|i| {
+            calls += 1;
+            match *i {
+                5 => RetainAction::StopKeepingRest,
+                2 => RetainAction::Keep,
+                _ => RetainAction::Remove,
+            }
+        });
+        assert_eq!(calls, 3);
+        assert_eq!(lvec.into_vec(), vec![2, 5, 3, 1, 2]);
+    }
+
+    #[test]
+    fn test_merge_insert_all() {
+        let mut lvec = LimitedVec::<u8, 6>::from(vec![1, 3, 5]);
+        lvec.merge_insert_all(&[2, 4]);
+        assert_eq!(lvec.clone().into_vec(), vec![1, 2, 3, 4, 5]);
+        lvec.merge_insert_all(&[]);
+        lvec.merge_insert_all(&[0]);
+        assert_eq!(lvec.clone().into_vec(), vec![0, 1, 2, 3, 4, 5]);
+
+        let mut lvec = LimitedVec::<(u8, char), 6>::from(vec![(1, 'a'), (2, 'a')]);
+        lvec.merge_insert_all(&[(1, 'b'), (3, 'b')]);
+        assert_eq!(
+            lvec.into_vec(),
+            vec![(1, 'a'), (1, 'b'), (2, 'a'), (3, 'b')]
+        );
+
+        let mut lvec = LimitedVec::<u8, 3>::from(vec![1, 3]);
+        let mut full = lvec.clone();
+        assert!(std::panic::catch_unwind(move || full.merge_insert_all(&[2, 4])).is_err());
+        lvec.merge_insert_all(&[2]);
+        assert_eq!(lvec.into_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(LimitedVec::<u8, 16>::describe(), "LimitedVec<u8, 16>");
+        let description = LimitedVec::<String, 3>::describe();
+        assert!(description.contains("String"));
+        assert!(description.ends_with(", 3>"));
+    }
+
+    #[test]
+    fn test_retain_with() {
+        let mut ids = LimitedVec::<u8, 4>::from(vec![1, 2, 3, 4]);
+        let mut names = LimitedVec::<&str, 6>::from(vec!["a", "b", "c", "d"]);
+        ids.retain_with(&mut names, |i| *i != 2);
+        assert_eq!(ids.clone().into_vec(), vec![1, 3, 4]);
+        assert_eq!(names.clone().into_vec(), vec!["a", "c", "d"]);
+
+        names.push("e");
+        assert!(std::panic::catch_unwind(move || ids.retain_with(&mut names, |_| true)).is_err());
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let lvec = LimitedVec::<u8, 8>::from_slice(&[1, 2, 3]);
+        assert_eq!(lvec.len(), 3);
+        assert_eq!(lvec.into_vec(), vec![1, 2, 3]);
+        assert!(LimitedVec::<u8, 0>::from_slice(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't create a LimitedVec with capacity of 2 from 3 items")]
+    fn test_from_slice_overflow() {
+        let _ = LimitedVec::<u8, 2>::from_slice(&[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_assert_fits() {
+        const _: () = LimitedVec::<u8, 8>::assert_fits::<8>();
+        LimitedVec::<String, 4>::assert_fits::<0>();
+    }
+
+    #[test]
+    fn test_pop_front_if_full() {
+        let mut window = LimitedVec::<u8, 3>::new();
+        for i in 1u8..=5 {
+            let evicted = window.pop_front_if_full();
+            assert_eq!(evicted, (i > 3).then(|| i - 3));
+            window.push(i);
+        }
+        assert_eq!(window.clone().into_vec(), vec![3, 4, 5]);
+
+        let _ = window.pop();
+        assert_eq!(window.pop_front_if_full(), None);
+        assert_eq!(window.len(), 2);
+
+        let mut empty = LimitedVec::<u8, 0>::new();
+        assert!(empty.is_full());
+        assert_eq!(empty.pop_front_if_full(), None);
+    }
+
+    #[test]
+    fn test_retain_stats() {
+        use super::RetainStats;
+
+        let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 6, 7]);
+        let stats = lvec.retain_stats(|i| i % 2 == 0);
+        assert_eq!(
+            stats,
+            RetainStats {
+                kept: 3,
+                removed: 3
+            }
+        );
+        assert_eq!(lvec.clone().into_vec(), vec![2, 4, 6]);
+
+        let stats = lvec.retain_stats(|_| true);
+        assert_eq!((stats.kept, stats.removed), (3, 0));
+    }
+
+    #[test]
+    fn test_iter_nth() {
+        let vec = vec![1u8, 2, 3, 4, 5, 6];
+        let lvec = LimitedVec::<u8, 8>::from(vec.clone());
+        for n in 0..8 {
+            assert_eq!(lvec.iter().nth(n), vec.get(n));
+            assert_eq!(lvec.iter().nth_back(n), vec.iter().nth_back(n));
+            assert_eq!(lvec.iter().rev().nth_back(n), vec.iter().rev().nth_back(n));
+        }
+
+        let mut iter = lvec.iter();
+        assert_eq!(iter.nth_back(2), Some(&4));
+        assert_eq!(iter.nth(1), Some(&2));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.nth_back(1), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_replace_at() {
+        let mut lvec = LimitedVec::<char, 4>::from(vec!['a', 'b', 'c']);
+        assert_eq!(lvec.replace_at(1, 'x'), 'b');
+        assert_eq!(lvec.clone().into_vec(), vec!['a', 'x', 'c']);
+        assert!(std::panic::catch_unwind(move || lvec.replace_at(3, 'y')).is_err());
+    }
+
+    #[test]
+    fn test_sub_views() {
+        let lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+        let sums = lvec
+            .windows(3)
+            .map(|w| w.iter().map(|i| *i as u32).sum::<u32>())
+            .collect::<Vec<_>>();
+        assert_eq!(sums, vec![6, 9, 12]);
+
+        let window = lvec.windows(2).nth(3).unwrap();
+        assert_eq!(
+            (window.len(), window.get(0), window.get(1)),
+            (2, Some(&4), Some(&5))
+        );
+        assert_eq!(window.get(2), None);
+        assert_eq!(lvec.windows(6).count(), 0);
+
+        let chunks = lvec
+            .chunks(2)
+            .map(|c| c.into_iter().copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert!(lvec.chunks(2).all(|c| !c.is_empty()));
+        assert_eq!(LimitedVec::<u8, 4>::new().chunks(3).count(), 0);
+    }
+
+    #[test]
+    fn test_into_iter_rev() {
+        let lvec = LimitedVec::<u8, 4>::from(vec![1, 2, 3]);
+        let reversed = lvec.into_iter().rev().collect::<LimitedVec<_, 4>>();
+        assert_eq!(reversed.clone().into_vec(), vec![3, 2, 1]);
+
+        let mut iter = reversed.into_iter();
+        assert_eq!(iter.next_back(), Some(1));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(2));
+        assert_eq!(iter.next(), None);
+
+        // DropCounter isn't Clone, so the items can only get moved
+        let (lvec, dropped) = drop_counters::<4>(3);
+        let reversed = lvec.into_iter().rev().collect::<LimitedVec<_, 4>>();
+        assert_eq!(ids(&reversed), vec![2, 1, 0]);
+        assert!(dropped.borrow().is_empty());
+        assert_all_dropped_once(reversed, &dropped, 3);
+    }
+
+    #[test]
+    fn test_saturating_extend() {
+        let mut driven = 0;
+        let mut lvec = LimitedVec::<u8, 4>::from(vec![1]);
+        lvec.saturating_extend((2..12).inspect(|_| driven += 1));
+        assert_eq!(driven, 10);
+        assert_eq!(lvec.clone().into_vec(), vec![1, 2, 3, 4]);
+
+        let (extra, dropped) = drop_counters::<4>(4);
+        let mut lvec = LimitedVec::<_, 2>::default();
+        lvec.saturating_extend(extra);
+        assert_eq!(ids(&lvec), vec![0, 1]);
+        assert_eq!(*dropped.borrow(), vec![2, 3]);
+        assert_all_dropped_once(lvec, &dropped, 4);
+    }
+
+    #[test]
+    fn test_into_vec_from() {
+        let lvec = LimitedVec::<String, 4>::from(vec!["a".to_string(), "b".to_string()]);
+        let vec: Vec<String> = lvec.into();
+        assert_eq!(vec, vec!["a", "b"]);
+        assert_eq!(Vec::from(LimitedVec::<u8, 2>::new()), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_drain_drop_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let (lvec, dropped) = drop_counters::<6>(5);
+        let mut lvec = lvec
+            .into_iter()
+            .map(PanicOnDrop)
+            .collect::<LimitedVec<_, 6>>();
+        let result = catch_unwind(AssertUnwindSafe(|| drop(lvec.drain(1..4))));
+        assert!(result.is_err());
+        assert_eq!(lvec.len(), 2);
+        assert_eq!(lvec.free(), 4);
+        assert_eq!(lvec.iter().map(|i| i.0.id).collect::<Vec<_>>(), vec![0, 4]);
+        assert_eq!(*dropped.borrow(), vec![1, 2, 3]);
+
+        drop(lvec);
+        assert_eq!(*dropped.borrow(), vec![1, 2, 3, 0, 4]);
+    }
+
+    #[test]
+    fn test_drain_forget() {
+        let (mut lvec, dropped) = drop_counters::<6>(5);
+        std::mem::forget(lvec.drain(1..3));
+        assert_eq!(ids(&lvec), vec![0, 3, 4]);
+        assert_eq!(lvec.free(), 3);
+        assert!(dropped.borrow().is_empty());
+
+        drop(lvec);
+        assert_eq!(*dropped.borrow(), vec![0, 3, 4]);
+    }
+
+    #[test]
+    fn test_drain_range_overflow() {
+        use std::ops::Bound;
+        use std::panic::catch_unwind;
+
+        let lvec = LimitedVec::<u8, 4>::from(vec![1, 2, 3]);
+        let mut inclusive = lvec.clone();
+        let err = catch_unwind(move || inclusive.drain(..=usize::MAX).count()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().unwrap(),
+            &format!("Range 0..={} out of bounds with length of 3", usize::MAX)
+        );
+
+        let mut excluded = lvec.clone();
+        let range = (Bound::Excluded(usize::MAX), Bound::Unbounded);
+        assert!(catch_unwind(move || excluded.drain(range).count()).is_err());
     }
 }
@@ -0,0 +1,53 @@
+use super::iter::Iter;
+use super::LimitedVec;
+use std::ops::Range;
+
+/// A read-only view of a contiguous range of items of a LimitedVec
+pub struct SubView<'a, T, const N: usize> {
+    lvec: &'a LimitedVec<T, N>,
+    range: Range<usize>,
+}
+
+impl<'a, T, const N: usize> SubView<'a, T, N> {
+    #[inline]
+    pub(crate) fn new(lvec: &'a LimitedVec<T, N>, range: Range<usize>) -> Self {
+        Self { lvec, range }
+    }
+
+    /// Returns the item at `pos` within the view or None if there is no such item
+    #[inline]
+    pub fn get(&self, pos: usize) -> Option<&'a T> {
+        if pos >= self.len() {
+            return None;
+        }
+        self.lvec.0[self.range.start + pos].as_ref()
+    }
+
+    /// Returns the amount of items within the view
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Returns `true` if the view doesn't contain any item
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Returns an iterator over the items within the view
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, T, N> {
+        Iter::with_range(self.lvec, self.range.clone())
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for SubView<'a, T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
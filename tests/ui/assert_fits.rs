@@ -0,0 +1,5 @@
+use limited_vec::LimitedVec;
+
+const _: () = LimitedVec::<u8, 4>::assert_fits::<5>();
+
+fn main() {}
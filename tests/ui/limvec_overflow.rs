@@ -0,0 +1,5 @@
+use limited_vec::limvec;
+
+fn main() {
+    let _ = limvec![cap = 2; 1, 2, 3];
+}
@@ -0,0 +1,12 @@
+#![deny(unused_must_use)]
+
+use limited_vec::LimitedVec;
+
+fn main() {
+    let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+    lvec.pop();
+    lvec.remove(0);
+    lvec.swap_remove(0);
+    lvec.split_off(1);
+    lvec.into_vec();
+}
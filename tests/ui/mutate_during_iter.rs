@@ -0,0 +1,10 @@
+use limited_vec::LimitedVec;
+
+fn main() {
+    let mut lvec = LimitedVec::<u8, 8>::from(vec![1, 2, 3, 4, 5]);
+    for item in lvec.iter() {
+        if *item == 2 {
+            lvec.truncate(1);
+        }
+    }
+}